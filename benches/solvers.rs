@@ -3,7 +3,7 @@ use std::time::Duration;
 use criterion::{
     BenchmarkGroup, Criterion, criterion_group, criterion_main, measurement::WallTime,
 };
-use rust_sudoku_solver::solvers::{backtracking::BacktrackingSolver, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver, Solver};
+use rust_sudoku_solver::solvers::{backtracking::BacktrackingSolver, backtracking_with_propagation::BacktrackingWithPropagationSolver, bitboard::BitboardSolver, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, dancing_links::DancingLinksSolver, forward_checking::ForwardCheckingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver, options::SolveOptions, portfolio::PortfolioSolver, sat::SatSolver, strategy::{SearchStrategy, ValueOrder, VariableOrder}, trail::TrailSolver, Solver};
 
 fn bench_solvers(c: &mut Criterion) {
     let mut group = c.benchmark_group("sudoku_solvers");
@@ -27,15 +27,56 @@ fn bench_solvers(c: &mut Criterion) {
     group.finish();
 }
 
+// The same solver with each search strategy, the default row major order is in bench_solvers
+fn bench_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_strategies");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(2));
+
+    let strategies = [
+        ("Mrv_Ascending", VariableOrder::Mrv, ValueOrder::Ascending),
+        ("Mrv_LeastConstraining", VariableOrder::Mrv, ValueOrder::LeastConstraining),
+        ("MrvDegree_Ascending", VariableOrder::MrvDegree, ValueOrder::Ascending),
+        ("MrvDegree_LeastConstraining", VariableOrder::MrvDegree, ValueOrder::LeastConstraining),
+    ];
+    for (name, variables, values) in strategies {
+        let options = SolveOptions {
+            strategy: SearchStrategy { variables, values },
+            ..SolveOptions::default()
+        };
+        for (name_extension, puzzle) in [
+            ("_simple", "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9"),
+            ("_hard", ".....9.......4..5.68.........4....7....62........8.......9..8.6........3..3..52.."),
+        ] {
+            group.bench_function(name.to_string() + name_extension, |b| {
+                b.iter(|| {
+                    assert!(GroupEliminatedBacktrackingSolver
+                        .solve_with(puzzle, &options)
+                        .is_ok_and(|(outcome, _)| outcome.is_solved()))
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
 fn bench_all_solvers(
     group: &mut BenchmarkGroup<'_, WallTime>,
     name_extension: &'static str,
-    puzzle: &'static str,
+    puzzle: &str,
 ) {
     let solvers: &[Box<dyn Solver>] = &[
         Box::new(BacktrackingSolver),
         Box::new(CellEliminatedBacktrackingSolver),
-        Box::new(GroupEliminatedBacktrackingSolver)
+        Box::new(GroupEliminatedBacktrackingSolver),
+        Box::new(BacktrackingWithPropagationSolver::default()),
+        Box::new(TrailSolver),
+        Box::new(ForwardCheckingSolver),
+        Box::new(DancingLinksSolver),
+        Box::new(BitboardSolver),
+        Box::new(SatSolver),
+        Box::new(PortfolioSolver::default())
     ];
     
     for solver in solvers {
@@ -47,12 +88,12 @@ fn bench_solver(
     group: &mut BenchmarkGroup<'_, WallTime>,
     solver: &dyn Solver,
     name_extension: &'static str,
-    puzzle: &'static str,
+    puzzle: &str,
 ) {
     group.bench_function(solver.name().to_string() + name_extension, |b| {
-        b.iter(|| assert!(solver.solve(puzzle).is_ok_and(|ret| ret.0 == true)))
+        b.iter(|| assert!(solver.solve(puzzle).is_ok_and(|outcome| outcome.is_solved())))
     });
 }
 
-criterion_group!(benches, bench_solvers);
+criterion_group!(benches, bench_solvers, bench_strategies);
 criterion_main!(benches);
@@ -0,0 +1,349 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    board::{Board, cell::simple_cell::SimpleCell},
+    error::SudokuError,
+    solvers::{SolveOutcome, Solver, options::SolveOptions},
+};
+
+#[cfg(test)]
+mod tests;
+
+// Solve times of a single solver over a whole batch
+pub struct SolverTimings {
+    pub name: &'static str,
+    pub times: Vec<Duration>,
+}
+
+pub struct BatchSummary {
+    pub solved: usize,
+    pub unsolvable: usize,
+    pub aborted: usize,
+    pub parse_errors: usize,
+    // Errors from any of the solvers on lines that did parse, a line can have more than one
+    pub solver_errors: usize,
+    pub timings: Vec<SolverTimings>,
+}
+
+impl SolverTimings {
+    pub fn min(&self) -> Option<Duration> {
+        self.times.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.times.iter().max().copied()
+    }
+
+    pub fn median(&self) -> Option<Duration> {
+        if self.times.is_empty() {
+            return None;
+        }
+        let mut sorted = self.times.clone();
+        sorted.sort_unstable();
+        Some(sorted[sorted.len() / 2])
+    }
+
+    pub fn total(&self) -> Duration {
+        self.times.iter().sum()
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Solved: {}", self.solved)?;
+        writeln!(f, "Unsolvable: {}", self.unsolvable)?;
+        if self.aborted > 0 {
+            writeln!(f, "Aborted: {}", self.aborted)?;
+        }
+        writeln!(f, "Parse errors: {}", self.parse_errors)?;
+        if self.solver_errors > 0 {
+            writeln!(f, "Solver errors: {}", self.solver_errors)?;
+        }
+        write!(f, "Name | [fastest, median, slowest] | total")?;
+        for timing in &self.timings {
+            match (timing.min(), timing.median(), timing.max()) {
+                (Some(min), Some(median), Some(max)) => write!(
+                    f,
+                    "\n{} | [{:?} {:?} {:?}] | {:?}",
+                    short_name(timing.name),
+                    min,
+                    median,
+                    max,
+                    timing.total()
+                )?,
+                _ => write!(f, "\n{} | no puzzles", short_name(timing.name))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Solver::name is the full type path, only the type itself is interesting here
+fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+// Written in place of a solution for lines that don't have one, an empty puzzle
+pub const NO_SOLUTION: &str = ".................................................................................";
+
+// What happened to a single line of the batch
+enum LineOutcome {
+    // Kept so the output lines up with the input
+    Blank,
+    Malformed(SudokuError),
+    // The first solver returned an error, it is in LineResult::errors
+    SolverFailed,
+    Finished(Box<SolveOutcome>),
+}
+
+struct LineResult {
+    line_number: usize,
+    outcome: LineOutcome,
+    // One per solver, empty for malformed lines
+    times: Vec<Duration>,
+    // Every solver that returned an error, with the error
+    errors: Vec<(&'static str, SudokuError)>,
+}
+
+// Solves every line of `input` with each of the solvers, one 81 character puzzle per line.
+// The limits in `options` apply to each puzzle separately.
+// Solutions from the first solver are written to `output` in input order, the other solvers
+// only contribute to the timings. Line N of the output is always for line N of the input: lines without
+// a solution get NO_SOLUTION, and blank lines stay blank.
+// Malformed, unsolvable and aborted lines, and errors from any of the solvers, are reported to `diagnostics`
+// with their (1 based) line number.
+pub fn solve_batch<R, W, D>(
+    input: R,
+    output: &mut W,
+    diagnostics: &mut D,
+    solvers: &[Box<dyn Solver>],
+    options: &SolveOptions,
+) -> io::Result<BatchSummary>
+where
+    R: BufRead,
+    W: Write,
+    D: Write,
+{
+    let mut summary = BatchSummary::new(solvers);
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let result = solve_line(index + 1, line.trim(), solvers, options);
+        summary.record(result, output, diagnostics)?;
+    }
+
+    output.flush()?;
+    Ok(summary)
+}
+
+// Same as solve_batch, but the puzzles are spread over `threads` worker threads.
+// Results are buffered until every earlier line is done, so the output order matches the input.
+pub fn solve_batch_parallel<R, W, D>(
+    input: R,
+    output: &mut W,
+    diagnostics: &mut D,
+    solvers: &[Box<dyn Solver>],
+    options: &SolveOptions,
+    threads: usize,
+) -> io::Result<BatchSummary>
+where
+    R: BufRead + Send,
+    W: Write,
+    D: Write,
+{
+    if threads <= 1 {
+        return solve_batch(input, output, diagnostics, solvers, options);
+    }
+
+    let mut summary = BatchSummary::new(solvers);
+
+    thread::scope(|scope| {
+        // Bounded so a huge file isn't read into memory faster than it can be solved
+        let (line_sender, line_receiver) = mpsc::sync_channel::<(usize, usize, String)>(threads * 16);
+        let line_receiver = Arc::new(Mutex::new(line_receiver));
+        let (result_sender, result_receiver) = mpsc::channel::<(usize, LineResult)>();
+        // One slot per line that has been read but not written yet. A slow line holds back the output,
+        // and this stops the other workers from piling up results behind it.
+        let window = threads * 32;
+        let (slot_sender, slot_receiver) = mpsc::sync_channel::<()>(window);
+        for _ in 0..window {
+            slot_sender.send(()).expect("room for every slot");
+        }
+
+        let reader = scope.spawn(move || -> io::Result<()> {
+            // Blank lines go through as well, so a line's index is its place in the output
+            for (index, line) in input.lines().enumerate() {
+                // Nothing is written any more once the slots are gone
+                if slot_receiver.recv().is_err() {
+                    break;
+                }
+                let line = line?;
+                // Every worker has hung up, nothing left to do
+                if line_sender.send((index, index + 1, line)).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        for _ in 0..threads {
+            let line_receiver = Arc::clone(&line_receiver);
+            let result_sender = result_sender.clone();
+            scope.spawn(move || {
+                loop {
+                    // The lock is released at the end of this statement, before solving
+                    let next = line_receiver.lock().expect("worker panicked").recv();
+                    let Ok((sequence, line_number, line)) = next else {
+                        break;
+                    };
+                    let result = solve_line(line_number, line.trim(), solvers, options);
+                    if result_sender.send((sequence, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Only the workers hold senders now, so the loop below ends once they are all done
+        drop(result_sender);
+
+        let mut pending: BTreeMap<usize, LineResult> = BTreeMap::new();
+        let mut next_sequence = 0;
+        for (sequence, result) in result_receiver {
+            pending.insert(sequence, result);
+            while let Some(result) = pending.remove(&next_sequence) {
+                summary.record(result, output, diagnostics)?;
+                next_sequence += 1;
+                // The reader may already be done, then nobody needs the slot
+                let _ = slot_sender.send(());
+            }
+        }
+
+        reader.join().expect("reader thread panicked")
+    })?;
+
+    output.flush()?;
+    Ok(summary)
+}
+
+fn solve_line(
+    line_number: usize,
+    puzzle: &str,
+    solvers: &[Box<dyn Solver>],
+    options: &SolveOptions,
+) -> LineResult {
+    let unsolved = |outcome| LineResult {
+        line_number,
+        outcome,
+        times: Vec::new(),
+        errors: Vec::new(),
+    };
+    if puzzle.is_empty() {
+        return unsolved(LineOutcome::Blank);
+    }
+    let board = match Board::<SimpleCell>::parse_puzzle_string(puzzle) {
+        Err(error) => return unsolved(LineOutcome::Malformed(error)),
+        Ok(board) => board,
+    };
+
+    let mut outcome = LineOutcome::Finished(Box::new(SolveOutcome::Unsolvable));
+    let mut times = Vec::with_capacity(solvers.len());
+    let mut errors = Vec::new();
+    for (solver_index, solver) in solvers.iter().enumerate() {
+        let start = Instant::now();
+        let solved = solver
+            .solve_board_with(&board, options)
+            .map(|(solve_outcome, _)| solve_outcome);
+        times.push(start.elapsed());
+
+        // Only the first solver decides the outcome, errors from any of them are reported
+        match solved {
+            Err(error) => {
+                if solver_index == 0 {
+                    outcome = LineOutcome::SolverFailed;
+                }
+                errors.push((solver.name(), error));
+            }
+            Ok(solve_outcome) if solver_index == 0 => outcome = LineOutcome::Finished(Box::new(solve_outcome)),
+            Ok(_) => {}
+        }
+    }
+
+    LineResult {
+        line_number,
+        outcome,
+        times,
+        errors,
+    }
+}
+
+impl BatchSummary {
+    fn new(solvers: &[Box<dyn Solver>]) -> Self {
+        BatchSummary {
+            solved: 0,
+            unsolvable: 0,
+            aborted: 0,
+            parse_errors: 0,
+            solver_errors: 0,
+            timings: solvers
+                .iter()
+                .map(|solver| SolverTimings {
+                    name: solver.name(),
+                    times: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn record<W, D>(&mut self, result: LineResult, output: &mut W, diagnostics: &mut D) -> io::Result<()>
+    where
+        W: Write,
+        D: Write,
+    {
+        for (timing, time) in self.timings.iter_mut().zip(result.times) {
+            timing.times.push(time);
+        }
+
+        let line_number = result.line_number;
+        for (solver, error) in result.errors {
+            self.solver_errors += 1;
+            writeln!(diagnostics, "line {line_number}: {} failed: {error}", short_name(solver))?;
+        }
+        match result.outcome {
+            LineOutcome::Blank => writeln!(output),
+            LineOutcome::Malformed(error) => {
+                self.parse_errors += 1;
+                writeln!(diagnostics, "line {line_number}: {error}")?;
+                writeln!(output, "{NO_SOLUTION}")
+            }
+            LineOutcome::SolverFailed => writeln!(output, "{NO_SOLUTION}"),
+            LineOutcome::Finished(outcome) => match *outcome {
+                SolveOutcome::Unsolvable => {
+                    self.unsolvable += 1;
+                    writeln!(diagnostics, "line {line_number}: unsolvable")?;
+                    writeln!(output, "{NO_SOLUTION}")
+                }
+                SolveOutcome::Aborted(reason) => {
+                    self.aborted += 1;
+                    writeln!(diagnostics, "line {line_number}: aborted, {reason}")?;
+                    writeln!(output, "{NO_SOLUTION}")
+                }
+                SolveOutcome::Solved(solution) => {
+                    self.solved += 1;
+                    writeln!(output, "{solution}")
+                }
+                // Still a valid solution, even if it is not the only one
+                SolveOutcome::MultipleSolutions { first, .. } => {
+                    self.solved += 1;
+                    writeln!(output, "{first}")
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    batch::{NO_SOLUTION, solve_batch, solve_batch_parallel},
+    board::{Board, cell::{CellPosition, simple_cell::SimpleCell}},
+    error::{Contradiction, SudokuError},
+    solvers::{SolutionSearch, Solver, backtracking::BacktrackingSolver, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, options::SolveOptions},
+};
+
+const INPUT: &str = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9
+8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6......29.7..a.6.34..9
+
+12345678.........9...............................................................
+.83.2..9....8..1...293....8....987...7.....6...674....3....698...2..5....1..3.54.
+";
+
+#[test]
+fn solves_every_line_and_reports_bad_ones() {
+    let solvers: Vec<Box<dyn Solver>> = vec![
+        Box::new(CellEliminatedBacktrackingSolver),
+        Box::new(BacktrackingSolver),
+    ];
+    let mut output = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let summary = solve_batch(INPUT.as_bytes(), &mut output, &mut diagnostics, &solvers, &SolveOptions::default())
+        .expect("batch failed");
+
+    // One output line per input line, so solutions can be matched up with their puzzles
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        format!(
+            "891457632234681597675392841183975264762148953459263718947816325318529476526734189
+{NO_SOLUTION}
+
+{NO_SOLUTION}
+183524697547869123629317458235698714471253869896741235354176982962485371718932546
+"
+        )
+    );
+    let diagnostics = String::from_utf8(diagnostics).unwrap();
+    assert!(diagnostics.contains("line 2: "));
+    assert!(diagnostics.contains("line 4: unsolvable"));
+
+    assert_eq!(summary.solved, 2);
+    assert_eq!(summary.unsolvable, 1);
+    assert_eq!(summary.parse_errors, 1);
+    assert_eq!(summary.timings.len(), 2);
+    assert!(summary.timings.iter().all(|timing| timing.times.len() == 3));
+}
+
+#[test]
+fn parallel_batch_keeps_input_order() {
+    let solvers: Vec<Box<dyn Solver>> = vec![Box::new(CellEliminatedBacktrackingSolver)];
+    let input = INPUT.repeat(8);
+    let mut sequential_output = Vec::new();
+    let mut parallel_output = Vec::new();
+    let mut parallel_diagnostics = Vec::new();
+
+    solve_batch(input.as_bytes(), &mut sequential_output, &mut Vec::new(), &solvers, &SolveOptions::default())
+        .expect("batch failed");
+    let summary = solve_batch_parallel(
+        input.as_bytes(),
+        &mut parallel_output,
+        &mut parallel_diagnostics,
+        &solvers,
+        &SolveOptions::default(),
+        4,
+    )
+    .expect("parallel batch failed");
+
+    assert_eq!(parallel_output, sequential_output);
+    let diagnostics = String::from_utf8(parallel_diagnostics).unwrap();
+    assert!(diagnostics.starts_with("line 2: "));
+    assert!(diagnostics.contains("line 39: unsolvable"));
+    assert_eq!(summary.solved, 16);
+    assert_eq!(summary.unsolvable, 8);
+    assert_eq!(summary.parse_errors, 8);
+}
+
+// Rejects every board the way the solvers that check entries reject ones that can't be solved
+struct Failing;
+
+impl Solver for Failing {
+    fn search<'a>(
+        &'a self,
+        _board: &Board<SimpleCell>,
+        _options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Err(SudokuError::Contradiction(Contradiction::NoCandidates(CellPosition { row: 0, column: 0 })))
+    }
+}
+
+#[test]
+fn reports_solver_errors_apart_from_parse_errors() {
+    let solvers: Vec<Box<dyn Solver>> = vec![Box::new(Failing), Box::new(BacktrackingSolver)];
+    let mut output = Vec::new();
+    let mut diagnostics = Vec::new();
+    let summary = solve_batch(INPUT.as_bytes(), &mut output, &mut diagnostics, &solvers, &SolveOptions::default())
+        .expect("batch failed");
+    assert_eq!(String::from_utf8(output).unwrap(), format!("{NO_SOLUTION}\n{NO_SOLUTION}\n\n{NO_SOLUTION}\n{NO_SOLUTION}\n"));
+    assert_eq!((summary.solved, summary.parse_errors, summary.solver_errors), (0, 1, 3));
+    let diagnostics = String::from_utf8(diagnostics).unwrap();
+    assert!(diagnostics.contains("line 1: Failing failed: The board can't be solved: No digit fits in r1c1."), "{diagnostics}");
+
+    // Errors from solvers after the first are reported too, the first one's answers still count
+    let solvers: Vec<Box<dyn Solver>> = vec![Box::new(BacktrackingSolver), Box::new(Failing)];
+    let mut diagnostics = Vec::new();
+    let summary = solve_batch(INPUT.as_bytes(), &mut Vec::new(), &mut diagnostics, &solvers, &SolveOptions::default())
+        .expect("batch failed");
+    assert_eq!((summary.solved, summary.unsolvable, summary.solver_errors), (2, 1, 3));
+    let diagnostics = String::from_utf8(diagnostics).unwrap();
+    assert!(diagnostics.contains("line 5: Failing failed: "), "{diagnostics}");
+}
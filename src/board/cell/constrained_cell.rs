@@ -4,6 +4,8 @@ use crate::board::{cell::{CellPosition, CellValue, IncrementResult, IsCell}, Boa
 pub enum ValueConstraint {
     Allowed,
     FixedNotAllowed,
+    // Ruled out by a value the search placed, backtracking past that value allows it again
+    SearchNotAllowed,
 }
 
 #[derive(Clone, PartialEq)]
@@ -44,7 +46,57 @@ impl ConstrainedCell {
             }
         }
     }
-    pub fn increment(&mut self) -> IncrementResult {
+    // Bit map of the values still allowed, bit 1 for value 1 and so on
+    pub fn candidates(&self) -> u16 {
+        (1..=9)
+            .filter(|&val| self.value_constraint_map[val] == ValueConstraint::Allowed)
+            .fold(0, |candidates, val| candidates | 1u16 << val)
+    }
+
+    pub(crate) fn get_next_not_constrained(&self, from: i8) -> Option<i8> {
+        // Clamp to the valid digit domain [1, 9]; short-circuit if above range.
+        let start = if from < 1 {
+            1
+        } else if from > 9 {
+            return None;
+        } else {
+            from
+        };
+        (start..=9)
+            .find(|&val| self.value_constraint_map[val as usize] == ValueConstraint::Allowed)
+    }}
+
+impl IsCell for ConstrainedCell {
+    fn value(&self) -> &CellValue {
+        &self.value
+    }
+
+    fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    fn position(&self) -> CellPosition {
+        self.position
+    }
+
+    fn new(value: CellValue, fixed: bool, position: CellPosition) -> Self {
+        ConstrainedCell {
+            value,
+            fixed,
+            position,
+            value_constraint_map: [ValueConstraint::Allowed; 10],
+        }
+    }
+
+    fn is_allowed(&self, value: i8) -> bool {
+        self.value_constraint_map[value as usize] == ValueConstraint::Allowed
+    }
+
+    fn set_value(&mut self, value: CellValue) {
+        self.value = value;
+    }
+
+    fn increment(&mut self) -> IncrementResult {
         match self.value {
             CellValue::Empty => match self.get_next_not_constrained(1) {
                 // Can't be incremented, set to empty and tell the backtracker to find a new cell
@@ -94,35 +146,4 @@ impl ConstrainedCell {
             }
         }
     }
-
-    fn get_next_not_constrained(&self, from: i8) -> Option<i8> {
-        // Clamp to the valid digit domain [1, 9]; short-circuit if above range.
-        let start = if from < 1 {
-            1
-        } else if from > 9 {
-            return None;
-        } else {
-            from
-        };
-        (start..=9)
-            .find(|&val| self.value_constraint_map[val as usize] == ValueConstraint::Allowed)
-    }}
-
-impl IsCell for ConstrainedCell {
-    fn value(&self) -> &CellValue {
-        &self.value
-    }
-
-    fn is_fixed(&self) -> bool {
-        self.fixed
-    }
-
-    fn new(value: CellValue, fixed: bool, position: CellPosition) -> Self {
-        ConstrainedCell {
-            value,
-            fixed,
-            position,
-            value_constraint_map: [ValueConstraint::Allowed; 10],
-        }
-    }
 }
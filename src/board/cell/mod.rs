@@ -6,7 +6,15 @@ pub mod constrained_cell;
 pub trait IsCell: Clone {
     fn value(&self) -> &CellValue;
     fn is_fixed(&self) -> bool;
+    fn position(&self) -> CellPosition;
     fn new(value: CellValue, fixed: bool, position: CellPosition) -> Self;
+    fn set_value(&mut self, value: CellValue);
+    // Moves the cell on to the next value the backtracker should try
+    fn increment(&mut self) -> IncrementResult;
+    // Whether the cell's own constraints allow the value, without looking at the rest of the board
+    fn is_allowed(&self, _value: i8) -> bool {
+        true
+    }
 }
 #[derive(Clone, PartialEq, Eq, Copy, Debug)]
 pub struct CellPosition {
@@ -14,6 +22,43 @@ pub struct CellPosition {
     pub column: i8
 }
 
+// 1 based, in the usual r<row>c<column> notation
+impl fmt::Display for CellPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "r{}c{}", self.row + 1, self.column + 1)
+    }
+}
+
+impl CellPosition {
+    // Whether the cells share a row, column or square, a cell doesn't see itself
+    pub fn sees(self, other: CellPosition) -> bool {
+        self != other
+            && (self.row == other.row
+                || self.column == other.column
+                || (self.row / 3 == other.row / 3 && self.column / 3 == other.column / 3))
+    }
+
+    // The 20 cells that see this one: the rest of its row, then its column, then its square
+    pub fn peers(self) -> impl Iterator<Item = CellPosition> {
+        let square_start_row = (self.row / 3) * 3;
+        let square_start_col = (self.column / 3) * 3;
+        let row = (0..9)
+            .filter(move |&column| column != self.column)
+            .map(move |column| CellPosition { row: self.row, column });
+        let column = (0..9)
+            .filter(move |&row| row != self.row)
+            .map(move |row| CellPosition { row, column: self.column });
+        // the rest of the square, without the cells already in the row or column
+        let square = (0..9)
+            .map(move |i| CellPosition {
+                row: square_start_row + i / 3,
+                column: square_start_col + i % 3,
+            })
+            .filter(move |peer| peer.row != self.row && peer.column != self.column);
+        row.chain(column).chain(square)
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum CellValue {
     Empty,
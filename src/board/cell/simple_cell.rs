@@ -1,6 +1,6 @@
 use crate::board::cell::{CellPosition, CellValue, IncrementResult, IsCell};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SimpleCell {
     pub value: CellValue,
     pub fixed: bool,
@@ -16,6 +16,10 @@ impl IsCell for SimpleCell {
         self.fixed
     }
 
+    fn position(&self) -> CellPosition {
+        self.position
+    }
+
     fn new(value: CellValue, fixed: bool, position: CellPosition) -> Self {
         SimpleCell {
             value,
@@ -23,10 +27,12 @@ impl IsCell for SimpleCell {
             position
         }
     }
-}
 
-impl SimpleCell {
-    pub fn increment(&mut self) -> IncrementResult {
+    fn set_value(&mut self, value: CellValue) {
+        self.value = value;
+    }
+
+    fn increment(&mut self) -> IncrementResult {
         match self.value {
             CellValue::Empty => {
                 self.value = CellValue::Filled(1);
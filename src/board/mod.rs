@@ -1,6 +1,9 @@
-use std::array;
+use std::{array, fmt};
 
-use crate::board::cell::{CellPosition, CellValue, IsCell};
+use crate::{
+    board::cell::{CellPosition, CellValue, IsCell},
+    error::{Contradiction, SudokuError},
+};
 
 pub mod cell;
 #[cfg(test)]
@@ -11,6 +14,69 @@ pub struct Board<T>(pub [[T; 9]; 9])
 where
     T: IsCell;
 
+// A row, column or square, numbered from 0 top to bottom and left to right
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Group {
+    Row(i8),
+    Column(i8),
+    Square(i8),
+}
+
+impl Group {
+    // Rows, then columns, then squares
+    pub fn all() -> impl Iterator<Item = Group> {
+        (0..9)
+            .map(Group::Row)
+            .chain((0..9).map(Group::Column))
+            .chain((0..9).map(Group::Square))
+    }
+
+    // The row, column and square the cell is in
+    pub fn containing(position: CellPosition) -> [Group; 3] {
+        [
+            Group::Row(position.row),
+            Group::Column(position.column),
+            Group::Square((position.row / 3) * 3 + position.column / 3),
+        ]
+    }
+
+    // Left to right, top to bottom
+    pub fn cells(self) -> impl Iterator<Item = CellPosition> {
+        (0..9).map(move |i| match self {
+            Group::Row(row) => CellPosition { row, column: i },
+            Group::Column(column) => CellPosition { row: i, column },
+            Group::Square(square) => CellPosition {
+                row: (square / 3) * 3 + i / 3,
+                column: (square % 3) * 3 + i % 3,
+            },
+        })
+    }
+}
+
+// 1 based, like CellPosition
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Group::Row(index) => write!(f, "row {}", index + 1),
+            Group::Column(index) => write!(f, "column {}", index + 1),
+            Group::Square(index) => write!(f, "square {}", index + 1),
+        }
+    }
+}
+
+// The 81 character form accepted by parse_puzzle_string
+impl<T> fmt::Display for Board<T>
+where
+    T: IsCell,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for cell in self.0.iter().flatten() {
+            write!(f, "{}", cell.value())?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Board<T>
 where
     T: IsCell,
@@ -100,6 +166,49 @@ where
             .rfind(|cell| *cell.value() != CellValue::Empty && !cell.is_fixed())
     }
 
+    // Whether a player has filled in any cells that aren't givens
+    pub fn has_entries(&self) -> bool {
+        self.0
+            .iter()
+            .flatten()
+            .any(|cell| *cell.value() != CellValue::Empty && !cell.is_fixed())
+    }
+
+    // A copy with the player's entries fixed as well, so a search takes them as assumptions
+    // and never changes them. Any other cell state (like constraints) is reset.
+    pub fn with_entries_fixed(&self) -> Board<T> {
+        Board::<T>(core::array::from_fn(|y| {
+            core::array::from_fn(|x| {
+                let cell = &self.0[y][x];
+                T::new(
+                    cell.value().clone(),
+                    cell.is_fixed() || *cell.value() != CellValue::Empty,
+                    cell.position(),
+                )
+            })
+        }))
+    }
+
+    // Copies the values and fixed flags into a board with a different cell type
+    pub fn convert<U>(&self) -> Board<U>
+    where
+        U: IsCell,
+    {
+        Board::<U>(core::array::from_fn(|y| {
+            core::array::from_fn(|x| {
+                let cell = &self.0[y][x];
+                U::new(
+                    cell.value().clone(),
+                    cell.is_fixed(),
+                    CellPosition {
+                        row: y as i8,
+                        column: x as i8,
+                    },
+                )
+            })
+        }))
+    }
+
     pub fn to_str(&self) -> String {
         self.0
             .iter()
@@ -114,7 +223,8 @@ where
             .reduce(|coll, substr| coll + substr.as_str())
             .expect("Failed to parse board to str")
     }
-    pub fn parse_puzzle_string(puzzle: &str) -> Result<Board<T>, &'static str> {
+    // Checks the format and that no two givens clash
+    pub fn parse_puzzle_string(puzzle: &str) -> Result<Board<T>, SudokuError> {
         match Self::check_puzzle_string_valid(puzzle) {
             Err(error) => return Err(error),
             Ok(_) => {}
@@ -144,6 +254,7 @@ where
         let board: Board<T> = Board::<T>(core::array::from_fn(|y| {
             core::array::from_fn(|x| flat[(y * 9) + x].clone())
         }));
+        board.check_givens()?;
 
         return Ok(board);
     }
@@ -167,14 +278,68 @@ where
         }
         return true;
     }
-    fn check_puzzle_string_valid(puzzle: &str) -> Result<(), &'static str> {
-        if puzzle.len() != 81 {
-            return Err("Input string has the wrong length.");
+    // Finds the first pair of givens that share a row, column or square and have the same digit
+    pub fn check_givens(&self) -> Result<(), SudokuError> {
+        match self.first_clash(true) {
+            Some((a, b, digit)) => Err(SudokuError::ConflictingGivens { a, b, digit }),
+            None => Ok(()),
+        }
+    }
+
+    // Like check_givens, but for every filled cell, given or entered by a player
+    pub fn find_clash(&self) -> Option<Contradiction> {
+        self.first_clash(false)
+            .map(|(a, b, digit)| Contradiction::Clash { a, b, digit })
+    }
+
+    fn first_clash(&self, givens_only: bool) -> Option<(CellPosition, CellPosition, i8)> {
+        // first cell seen for each digit, per row/column/square
+        let mut row_seen = [[None; 10]; 9];
+        let mut col_seen = [[None; 10]; 9];
+        let mut square_seen = [[None; 10]; 9];
+
+        for (row_index, row) in self.0.iter().enumerate() {
+            for (col_index, cell) in row.iter().enumerate() {
+                let CellValue::Filled(digit) = cell.value() else {
+                    continue;
+                };
+                if givens_only && !cell.is_fixed() {
+                    continue;
+                }
+                let position = CellPosition {
+                    row: row_index as i8,
+                    column: col_index as i8,
+                };
+                let square_index = ((row_index / 3) * 3) + (col_index / 3);
+                for seen in [
+                    &mut row_seen[row_index],
+                    &mut col_seen[col_index],
+                    &mut square_seen[square_index],
+                ] {
+                    match seen[*digit as usize] {
+                        Some(other) => return Some((other, position, *digit)),
+                        None => seen[*digit as usize] = Some(position),
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn check_puzzle_string_valid(puzzle: &str) -> Result<(), SudokuError> {
+        let found = puzzle.chars().count();
+        if found != 81 {
+            return Err(SudokuError::WrongLength { found });
         }
 
-        if !puzzle.chars().all(|c| c == '.' || (c.is_ascii_digit() && c != '0')) {
-            return Err("Invalid char in input string. Only '.' and ASCII digits 1-9 are allowed.");
+        if let Some((index, ch)) = puzzle
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !(*c == '.' || (c.is_ascii_digit() && *c != '0')))
+        {
+            return Err(SudokuError::InvalidChar { index, ch });
         }
 
         return Ok(());
-    }}
+    }
+}
@@ -1,4 +1,7 @@
-use crate::board::cell::simple_cell::SimpleCell;
+use crate::{
+    board::cell::{CellPosition, CellValue, simple_cell::SimpleCell},
+    error::{Contradiction, SudokuError},
+};
 
 #[test]
 fn parse_valid_board() {
@@ -19,7 +22,7 @@ fn dont_parse_invalid_board_invalid_char() {
 fn dont_parse_invalid_board_too_short() {
     let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6......29.7...6.34..9";
     let board = crate::board::Board::<SimpleCell>::parse_puzzle_string(puzzle);
-    assert!(board.is_err());
+    assert_eq!(board.err(), Some(SudokuError::WrongLength { found: 80 }));
 }
 
 #[test]
@@ -29,3 +32,54 @@ fn dont_parse_invalid_board_too_long() {
     let board = crate::board::Board::<SimpleCell>::parse_puzzle_string(puzzle);
     assert!(board.is_err());
 }
+
+#[test]
+fn dont_parse_invalid_board_conflicting_givens() {
+    let puzzle =
+        "4..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let board = crate::board::Board::<SimpleCell>::parse_puzzle_string(puzzle);
+    assert_eq!(
+        board.err(),
+        Some(SudokuError::ConflictingGivens {
+            a: CellPosition { row: 0, column: 0 },
+            b: CellPosition { row: 0, column: 3 },
+            digit: 4,
+        })
+    );
+}
+
+#[test]
+fn find_clash_includes_entries() {
+    let puzzle =
+        "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let mut board = crate::board::Board::<SimpleCell>::parse_puzzle_string(puzzle).expect("valid puzzle");
+    assert!(!board.has_entries());
+    board.get_mut_cell(&CellPosition { row: 0, column: 1 }).value = CellValue::Filled(8);
+    assert!(board.has_entries());
+    // Only the entry clashes, so the givens are still fine
+    assert_eq!(board.check_givens(), Ok(()));
+    assert_eq!(
+        board.find_clash(),
+        Some(Contradiction::Clash {
+            a: CellPosition { row: 0, column: 0 },
+            b: CellPosition { row: 0, column: 1 },
+            digit: 8,
+        })
+    );
+}
+
+#[test]
+fn finds_peers() {
+    let position = CellPosition { row: 4, column: 4 };
+    let peers: Vec<_> = position.peers().collect();
+    assert_eq!(peers.len(), 20);
+    for row in 0..9 {
+        for column in 0..9 {
+            let other = CellPosition { row, column };
+            assert_eq!(position.sees(other), peers.contains(&other), "{other}");
+        }
+    }
+    assert!(!position.sees(position));
+    assert!(CellPosition { row: 3, column: 5 }.sees(position));
+    assert!(!CellPosition { row: 2, column: 5 }.sees(position));
+}
@@ -0,0 +1,293 @@
+use std::{fs, time::Duration};
+
+use rust_sudoku_solver::solvers::options::SolveOptions;
+
+#[cfg(test)]
+mod tests;
+
+pub const USAGE: &str = "Usage:
+    rust-sudoku-solver solve [--solver <name>] [--rules <rule>[,<rule>...]] [--format <grid|line>] [--stats] [limits] (<puzzle> | --file <path>)
+    rust-sudoku-solver batch [--solver <name>[,<name>...]] [--rules <rule>[,<rule>...]] [--output <path>] [--threads <n>] [limits] <puzzle file>
+    rust-sudoku-solver check [--solver <name>] [--rules <rule>[,<rule>...]] (<puzzle> | --file <path>)
+    rust-sudoku-solver explain [--rules <rule>[,<rule>...]] (<puzzle> | --file <path>)
+    rust-sudoku-solver list-solvers
+    rust-sudoku-solver help
+
+A puzzle is 81 characters, '.' for empty cells and 1-9 for givens.
+Puzzle files may spread the 81 characters over several lines.
+Batch files hold one puzzle per line, use - to read them from stdin.
+
+Options:
+    -s, --solver <name>    Solver to use (see list-solvers), default: groupeliminated
+                           batch accepts a comma separated list to compare timings,
+                           solutions come from the first solver
+    -r, --rules <rules>    Comma separated elimination rules for the propagation solver,
+                           run in order: naked-single, hidden-single, pointing,
+                           claiming, naked-pair, hidden-pair, naked-triple,
+                           hidden-triple, naked-quad, hidden-quad, x-wing,
+                           swordfish, jellyfish, finned-x-wing, finned-swordfish,
+                           finned-jellyfish, xy-wing, xyz-wing, w-wing
+                           Picks the propagation solver if --solver isn't given,
+                           for explain these are the techniques it may use, default: all
+    -f, --format <style>   Output style: grid (default) or line
+        --file <path>      Read the puzzle from a file instead of the command line
+        --stats            Print search statistics after solving
+    -o, --output <path>    Write batch solutions to a file instead of stdout
+    -t, --threads <n>      Worker threads for batch, default: one per core
+
+Limits (per puzzle):
+        --timeout <secs>   Give up after this many seconds, fractions are allowed
+        --node-budget <n>  Give up after this many guesses";
+
+pub const DEFAULT_SOLVER: &str = "groupeliminated";
+// The only solver --rules applies to
+pub const PROPAGATION_SOLVER: &str = "propagation";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Solve {
+        puzzle: PuzzleSource,
+        solver: String,
+        rules: Option<Vec<String>>,
+        format: OutputFormat,
+        stats: bool,
+        limits: Limits,
+    },
+    Batch {
+        input: String,
+        output: Option<String>,
+        solvers: Vec<String>,
+        rules: Option<Vec<String>>,
+        threads: Option<usize>,
+        limits: Limits,
+    },
+    Check {
+        puzzle: PuzzleSource,
+        solver: String,
+        rules: Option<Vec<String>>,
+    },
+    // Solves by logic alone and prints every step
+    Explain {
+        puzzle: PuzzleSource,
+        rules: Option<Vec<String>>,
+    },
+    ListSolvers,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PuzzleSource {
+    Inline(String),
+    File(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputFormat {
+    // 9x9 grid with box separators, plus timing information
+    Grid,
+    // Only the 81 character solution, for use in scripts
+    Line,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    pub node_budget: Option<u64>,
+}
+
+impl Limits {
+    fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.node_budget.is_some()
+    }
+
+    pub fn to_options(&self) -> SolveOptions {
+        SolveOptions {
+            timeout: self.timeout,
+            node_budget: self.node_budget,
+            ..SolveOptions::default()
+        }
+    }
+}
+
+impl PuzzleSource {
+    // Whitespace is stripped so files can hold the puzzle as 9 lines of 9
+    pub fn read(&self) -> Result<String, String> {
+        let raw = match self {
+            PuzzleSource::Inline(puzzle) => puzzle.clone(),
+            PuzzleSource::File(path) => fs::read_to_string(path)
+                .map_err(|error| format!("Failed to read puzzle file {path}: {error}"))?,
+        };
+        Ok(raw.chars().filter(|c| !c.is_whitespace()).collect())
+    }
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        None => return Ok(Command::Help),
+        Some(command) => command,
+    };
+
+    let mut solver: Option<String> = None;
+    let mut rules: Option<Vec<String>> = None;
+    let mut format: Option<OutputFormat> = None;
+    let mut file: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut threads: Option<usize> = None;
+    let mut stats = false;
+    let mut limits = Limits::default();
+    let mut positional: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-s" | "--solver" => solver = Some(expect_value(&arg, args.next())?),
+            "-r" | "--rules" => rules = Some(split_list(&expect_value(&arg, args.next())?)),
+            "-f" | "--format" => {
+                format = Some(match expect_value(&arg, args.next())?.as_str() {
+                    "grid" => OutputFormat::Grid,
+                    "line" => OutputFormat::Line,
+                    other => return Err(format!("Unknown output format: {other}")),
+                })
+            }
+            "--file" => file = Some(expect_value(&arg, args.next())?),
+            "-o" | "--output" => output = Some(expect_value(&arg, args.next())?),
+            "-t" | "--threads" => {
+                let value = expect_value(&arg, args.next())?;
+                threads = match value.parse::<usize>() {
+                    Ok(count) if count > 0 => Some(count),
+                    _ => return Err(format!("Invalid thread count: {value}")),
+                }
+            }
+            "--stats" => stats = true,
+            "--timeout" => {
+                let value = expect_value(&arg, args.next())?;
+                // try_from_secs_f64 rejects negative, infinite and too large values
+                limits.timeout = match value.parse::<f64>().map(Duration::try_from_secs_f64) {
+                    Ok(Ok(timeout)) if !timeout.is_zero() => Some(timeout),
+                    _ => return Err(format!("Invalid timeout: {value}")),
+                }
+            }
+            "--node-budget" => {
+                let value = expect_value(&arg, args.next())?;
+                limits.node_budget = match value.parse::<u64>() {
+                    Ok(budget) => Some(budget),
+                    _ => return Err(format!("Invalid node budget: {value}")),
+                }
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            // a lone - means stdin
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                return Err(format!("Unknown option: {arg}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    // explain doesn't take a solver, the rest pick the propagation solver for --rules
+    if rules.is_some() && command != "explain" {
+        match &solver {
+            None => solver = Some(PROPAGATION_SOLVER.to_string()),
+            Some(names) if split_list(names).iter().any(|name| name.eq_ignore_ascii_case(PROPAGATION_SOLVER)) => {}
+            Some(_) => return Err(format!("--rules only applies to the {PROPAGATION_SOLVER} solver")),
+        }
+    }
+
+    match command.as_str() {
+        "solve" => {
+            reject_option("solve", "--output", output.is_some())?;
+            reject_option("solve", "--threads", threads.is_some())?;
+            Ok(Command::Solve {
+                puzzle: puzzle_source(positional, file)?,
+                solver: solver.unwrap_or_else(|| DEFAULT_SOLVER.to_string()),
+                rules,
+                format: format.unwrap_or(OutputFormat::Grid),
+                stats,
+                limits,
+            })
+        }
+        "batch" => {
+            reject_option("batch", "--format", format.is_some())?;
+            reject_option("batch", "--stats", stats)?;
+            reject_option("batch", "--file", file.is_some())?;
+            if positional.len() != 1 {
+                return Err("Expected exactly one puzzle file".to_string());
+            }
+            Ok(Command::Batch {
+                input: positional.remove(0),
+                output,
+                solvers: split_list(&solver.unwrap_or_else(|| DEFAULT_SOLVER.to_string())),
+                rules,
+                threads,
+                limits,
+            })
+        }
+        "check" => {
+            reject_option("check", "--format", format.is_some())?;
+            reject_option("check", "--output", output.is_some())?;
+            reject_option("check", "--threads", threads.is_some())?;
+            reject_option("check", "--stats", stats)?;
+            reject_option("check", "--timeout or --node-budget", limits.is_set())?;
+            Ok(Command::Check {
+                puzzle: puzzle_source(positional, file)?,
+                solver: solver.unwrap_or_else(|| DEFAULT_SOLVER.to_string()),
+                rules,
+            })
+        }
+        "explain" => {
+            reject_option("explain", "--solver", solver.is_some())?;
+            reject_option("explain", "--format", format.is_some())?;
+            reject_option("explain", "--output", output.is_some())?;
+            reject_option("explain", "--threads", threads.is_some())?;
+            reject_option("explain", "--stats", stats)?;
+            reject_option("explain", "--timeout or --node-budget", limits.is_set())?;
+            Ok(Command::Explain {
+                puzzle: puzzle_source(positional, file)?,
+                rules,
+            })
+        }
+        "list-solvers" => {
+            if !positional.is_empty()
+                || solver.is_some()
+                || rules.is_some()
+                || format.is_some()
+                || file.is_some()
+                || output.is_some()
+                || threads.is_some()
+                || stats
+                || limits.is_set()
+            {
+                return Err("list-solvers takes no arguments".to_string());
+            }
+            Ok(Command::ListSolvers)
+        }
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        other => Err(format!("Unknown command: {other}")),
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(|name| name.trim().to_string()).collect()
+}
+
+fn expect_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("Missing value for {option}"))
+}
+
+fn reject_option(command: &str, option: &str, present: bool) -> Result<(), String> {
+    if present {
+        return Err(format!("{command} does not accept {option}"));
+    }
+    Ok(())
+}
+
+fn puzzle_source(mut positional: Vec<String>, file: Option<String>) -> Result<PuzzleSource, String> {
+    match (positional.len(), file) {
+        (0, Some(path)) => Ok(PuzzleSource::File(path)),
+        (1, None) => Ok(PuzzleSource::Inline(positional.remove(0))),
+        (0, None) => Err("Missing puzzle: pass it as an argument or with --file".to_string()),
+        _ => Err("Expected exactly one puzzle".to_string()),
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use crate::cli::{Command, DEFAULT_SOLVER, Limits, OutputFormat, PROPAGATION_SOLVER, PuzzleSource, parse_args};
+
+fn parse(args: &[&str]) -> Result<Command, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn parses_solve_with_defaults() {
+    assert_eq!(
+        parse(&["solve", "8..45.6..."]),
+        Ok(Command::Solve {
+            puzzle: PuzzleSource::Inline("8..45.6...".to_string()),
+            solver: DEFAULT_SOLVER.to_string(),
+            rules: None,
+            format: OutputFormat::Grid,
+            stats: false,
+            limits: Limits::default(),
+        })
+    );
+}
+
+#[test]
+fn parses_solve_with_options() {
+    assert_eq!(
+        parse(&[
+            "solve", "--file", "puzzle.txt", "-s", "backtracking", "--format", "line", "--stats", "--timeout", "1.5",
+            "--node-budget", "1000",
+        ]),
+        Ok(Command::Solve {
+            puzzle: PuzzleSource::File("puzzle.txt".to_string()),
+            solver: "backtracking".to_string(),
+            rules: None,
+            format: OutputFormat::Line,
+            stats: true,
+            limits: Limits {
+                timeout: Some(Duration::from_millis(1500)),
+                node_budget: Some(1000),
+            },
+        })
+    );
+}
+
+#[test]
+fn rejects_missing_or_duplicate_puzzle() {
+    assert!(parse(&["solve"]).is_err());
+    assert!(parse(&["solve", "abc", "--file", "puzzle.txt"]).is_err());
+    assert!(parse(&["check", "abc", "def"]).is_err());
+}
+
+#[test]
+fn rejects_unknown_command_and_options() {
+    assert!(parse(&["solv", "abc"]).is_err());
+    assert!(parse(&["solve", "abc", "--fast"]).is_err());
+    assert!(parse(&["solve", "abc", "--format", "fancy"]).is_err());
+    assert!(parse(&["check", "abc", "--format", "line"]).is_err());
+}
+
+#[test]
+fn no_arguments_prints_help() {
+    assert_eq!(parse(&[]), Ok(Command::Help));
+    assert_eq!(parse(&["list-solvers"]), Ok(Command::ListSolvers));
+}
+
+#[test]
+fn parses_batch_with_solver_list() {
+    assert_eq!(
+        parse(&["batch", "corpus.txt", "-s", "backtracking, celleliminated", "-o", "out.txt", "-t", "4"]),
+        Ok(Command::Batch {
+            input: "corpus.txt".to_string(),
+            output: Some("out.txt".to_string()),
+            solvers: vec!["backtracking".to_string(), "celleliminated".to_string()],
+            rules: None,
+            threads: Some(4),
+            limits: Limits::default(),
+        })
+    );
+    assert!(parse(&["batch", "corpus.txt", "--timeout", "-1"]).is_err());
+    assert!(parse(&["batch", "corpus.txt", "--timeout", "1e30"]).is_err());
+    assert!(parse(&["batch", "corpus.txt", "--threads", "0"]).is_err());
+    assert!(parse(&["solve", "abc", "--output", "out.txt"]).is_err());
+}
+
+#[test]
+fn parses_rules_for_the_propagation_solver() {
+    assert_eq!(
+        parse(&["check", "abc", "--rules", "hidden-single, naked-single"]),
+        Ok(Command::Check {
+            puzzle: PuzzleSource::Inline("abc".to_string()),
+            solver: PROPAGATION_SOLVER.to_string(),
+            rules: Some(vec!["hidden-single".to_string(), "naked-single".to_string()]),
+        })
+    );
+    assert!(parse(&["batch", "corpus.txt", "-s", "dlx,propagation", "-r", "naked-single"]).is_ok());
+    assert!(parse(&["solve", "abc", "-s", "dlx", "--rules", "naked-single"]).is_err());
+    assert!(parse(&["list-solvers", "--rules", "naked-single"]).is_err());
+}
+
+#[test]
+fn parses_explain() {
+    assert_eq!(
+        parse(&["explain", "--file", "puzzle.txt", "--rules", "naked-single"]),
+        Ok(Command::Explain {
+            puzzle: PuzzleSource::File("puzzle.txt".to_string()),
+            rules: Some(vec!["naked-single".to_string()]),
+        })
+    );
+    assert!(parse(&["explain", "abc", "--solver", "dlx"]).is_err());
+    assert!(parse(&["explain", "abc", "--timeout", "1"]).is_err());
+}
@@ -0,0 +1,76 @@
+use std::{error::Error, fmt};
+
+use crate::board::{Group, cell::CellPosition};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SudokuError {
+    // The puzzle string is not 81 characters long
+    WrongLength { found: usize },
+    // `index` is the position of the character in the puzzle string
+    InvalidChar { index: usize, ch: char },
+    // Two givens with the same digit share a row, column or square
+    ConflictingGivens {
+        a: CellPosition,
+        b: CellPosition,
+        digit: i8,
+    },
+    UnknownSolver(String),
+    // Not one of the propagation rules, see RULE_NAMES
+    UnknownRule(String),
+    // The solver stops at the first solution it finds, so it can't count them
+    NotExhaustive(&'static str),
+    // The player's entries can't all be right, see Contradiction
+    Contradiction(Contradiction),
+}
+
+// Found while pre-processing a board, the givens and entries it holds can't all be kept
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Contradiction {
+    // Two filled cells with the same digit share a row, column or square
+    Clash {
+        a: CellPosition,
+        b: CellPosition,
+        digit: i8,
+    },
+    // No digit fits in the empty cell
+    NoCandidates(CellPosition),
+    // The digit isn't in the group yet, and none of its empty cells can take it
+    NoPlaceFor { digit: i8, group: Group },
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contradiction::Clash { a, b, digit } => write!(f, "{a} and {b} are both {digit}."),
+            Contradiction::NoCandidates(position) => write!(f, "No digit fits in {position}."),
+            Contradiction::NoPlaceFor { digit, group } => write!(f, "{digit} has nowhere to go in {group}."),
+        }
+    }
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::WrongLength { found } => write!(
+                f,
+                "Input string has the wrong length: expected 81 characters, found {found}."
+            ),
+            SudokuError::InvalidChar { index, ch } => write!(
+                f,
+                "Invalid char {ch:?} at index {index} in input string. Only '.' and ASCII digits 1-9 are allowed."
+            ),
+            SudokuError::ConflictingGivens { a, b, digit } => {
+                write!(f, "Givens at {a} and {b} are both {digit}.")
+            }
+            SudokuError::UnknownSolver(name) => write!(f, "Unknown solver type: {name}"),
+            SudokuError::UnknownRule(name) => write!(f, "Unknown propagation rule: {name}"),
+            SudokuError::NotExhaustive(name) => write!(
+                f,
+                "{name} only finds one solution, so it can't count solutions or check they are unique."
+            ),
+            SudokuError::Contradiction(contradiction) => write!(f, "The board can't be solved: {contradiction}"),
+        }
+    }
+}
+
+impl Error for SudokuError {}
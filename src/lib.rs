@@ -1,3 +1,5 @@
 // src/lib.rs
+pub mod batch;
 pub mod board;
+pub mod error;
 pub mod solvers;
\ No newline at end of file
@@ -7,75 +7,227 @@
  * 					Best time: 2.4 ms
 */
 use std::{
-    process::ExitCode, time::Instant
+    env,
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    process::ExitCode,
+    thread,
+    time::Instant,
 };
 
-mod board;
-mod solvers;
+use rust_sudoku_solver::{
+    batch::solve_batch_parallel,
+    board::{Board, cell::simple_cell::SimpleCell},
+    error::SudokuError,
+    solvers::{
+        SOLVER_NAMES, SolveOutcome, Solver, backtracking_with_propagation::BacktrackingWithPropagationSolver,
+        get_solver, logical::LogicalSolver, options::SolveOptions, propagation::Pipeline,
+    },
+};
 
-use crate::solvers::get_solver;
+use crate::cli::{Command, OutputFormat, PROPAGATION_SOLVER, PuzzleSource, USAGE};
 
+mod cli;
 
 fn main() -> ExitCode {
-    // TODO: get these from args
-    let puzzle =
-        ".83.2..9....8..1...293....8....987...7.....6...674....3....698...2..5....1..3.54.";
-    
-    let solver = match get_solver("groupeliminated") {
+    let command = match cli::parse_args(env::args().skip(1)) {
         Err(msg) => {
-            eprintln!("{}", msg);
+            eprintln!("{msg}\n\n{USAGE}");
             return ExitCode::FAILURE;
-        },
-        Ok(val) => val
+        }
+        Ok(command) => command,
     };
-    
-    println!("Board:");
-    print_puzzle(puzzle.to_string());
 
+    let result = match command {
+        Command::Solve {
+            puzzle,
+            solver,
+            rules,
+            format,
+            stats,
+            limits,
+        } => solve(&puzzle, &solver, rules.as_deref(), format, stats, &limits.to_options()),
+        Command::Batch {
+            input,
+            output,
+            solvers,
+            rules,
+            threads,
+            limits,
+        } => batch(&input, output.as_deref(), &solvers, rules.as_deref(), threads, &limits.to_options()),
+        Command::Check { puzzle, solver, rules } => check(&puzzle, &solver, rules.as_deref()),
+        Command::Explain { puzzle, rules } => explain(&puzzle, rules.as_deref()),
+        Command::ListSolvers => {
+            for name in SOLVER_NAMES {
+                println!("{name}");
+            }
+            Ok(())
+        }
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+    };
+
+    match result {
+        Err(msg) => {
+            eprintln!("{msg}");
+            ExitCode::FAILURE
+        }
+        Ok(()) => ExitCode::SUCCESS,
+    }
+}
+
+// Like get_solver, with the propagation solver running `rules` if they are given
+fn make_solver(name: &str, rules: Option<&[String]>) -> Result<Box<dyn Solver>, SudokuError> {
+    match rules {
+        Some(rules) if name.eq_ignore_ascii_case(PROPAGATION_SOLVER) => {
+            let rules: Vec<&str> = rules.iter().map(String::as_str).collect();
+            Ok(Box::new(BacktrackingWithPropagationSolver::new(Pipeline::from_names(&rules)?)))
+        }
+        _ => get_solver(name),
+    }
+}
+
+fn solve(
+    source: &PuzzleSource,
+    solver_name: &str,
+    rules: Option<&[String]>,
+    format: OutputFormat,
+    print_stats: bool,
+    options: &SolveOptions,
+) -> Result<(), Box<dyn Error>> {
+    let solver = make_solver(solver_name, rules)?;
+    let puzzle = source.read()?;
+    // Reject malformed input before trying to print it
+    let board = Board::<SimpleCell>::parse_puzzle_string(&puzzle)?;
+
+    if format == OutputFormat::Grid {
+        println!("Board:");
+        print_board(&board);
+    }
 
     let start = Instant::now();
-    let solved = solver.solve(&puzzle);
+    let solved = solver.solve_board_with(&board, options);
     let duration = start.elapsed();
-    println!("Time to solve is: {:?}", duration);
-
-    match solved {
-        Err(error) => println!("Error while solving: {error}"),
-        Ok((solved, solved_board)) => {
-            if !solved {
-                println!("Unsolvable...");
-            } else {
-                println!("Solution:");
-                print_puzzle(solved_board);
-            }
+
+    let (outcome, stats) = solved.map_err(|error| format!("Error while solving: {error}"))?;
+    if print_stats {
+        // Keep stdout to just the solution in line format
+        match format {
+            OutputFormat::Grid => println!("{stats}"),
+            OutputFormat::Line => eprintln!("{stats}"),
+        }
+    }
+    let solved_board = match outcome {
+        SolveOutcome::Solved(board) => board,
+        SolveOutcome::Unsolvable => return Err("Unsolvable...".into()),
+        SolveOutcome::MultipleSolutions { first, .. } => first,
+        SolveOutcome::Aborted(reason) => return Err(format!("Gave up: {reason}").into()),
+    };
+
+    match format {
+        OutputFormat::Grid => {
+            println!("Time to solve is: {:?}", duration);
+            println!("Solution:");
+            print_board(&solved_board);
         }
+        OutputFormat::Line => println!("{solved_board}"),
     }
-    return ExitCode::SUCCESS;
+    Ok(())
 }
 
-fn print_puzzle(puzzle: String) {
-    let chars: Vec<char> = puzzle.chars().collect();
-    let size = 9;
+fn batch(
+    input: &str,
+    output: Option<&str>,
+    solver_names: &[String],
+    rules: Option<&[String]>,
+    threads: Option<usize>,
+    options: &SolveOptions,
+) -> Result<(), Box<dyn Error>> {
+    let solvers = solver_names
+        .iter()
+        .map(|name| make_solver(name, rules))
+        .collect::<Result<Vec<Box<dyn Solver>>, SudokuError>>()?;
 
-    for row in 0..size {
-        if row % 3 == 0 && row != 0 {
-            println!("------+-------+------");
+    let threads = threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |count| count.get()));
+
+    let reader: Box<dyn BufRead + Send> = if input == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        let file = File::open(input)
+            .map_err(|error| format!("Failed to open puzzle file {input}: {error}"))?;
+        Box::new(BufReader::new(file))
+    };
+    let mut writer: Box<dyn Write> = match output {
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path)
+                .map_err(|error| format!("Failed to create output file {path}: {error}"))?,
+        )),
+    };
+
+    let summary = solve_batch_parallel(reader, &mut writer, &mut io::stderr(), &solvers, options, threads)
+        .map_err(|error| format!("Batch failed: {error}"))?;
+    eprintln!("{summary}");
+    Ok(())
+}
+
+// A valid puzzle is well formed, its givens don't clash and it has exactly one solution
+fn check(source: &PuzzleSource, solver_name: &str, rules: Option<&[String]>) -> Result<(), Box<dyn Error>> {
+    let solver = make_solver(solver_name, rules)?;
+    let puzzle = source.read()?;
+    match solver.solve_unique(&puzzle)? {
+        SolveOutcome::Solved(_) => {
+            println!("Valid puzzle with a unique solution.");
+            Ok(())
         }
+        SolveOutcome::Unsolvable => Err("Invalid puzzle: it has no solution.".into()),
+        SolveOutcome::MultipleSolutions { first, second } => Err(format!(
+            "Invalid puzzle: it has more than one solution, for example\n{first}\n{second}"
+        )
+        .into()),
+        SolveOutcome::Aborted(reason) => Err(format!("Gave up: {reason}").into()),
+    }
+}
 
-        for col in 0..size {
-            if col % 3 == 0 && col != 0 {
-                print!("| ");
-            }
+// Every step logic takes, and where it got stuck if it couldn't finish
+fn explain(source: &PuzzleSource, rules: Option<&[String]>) -> Result<(), Box<dyn Error>> {
+    let solver = match rules {
+        Some(rules) => LogicalSolver::from_names(&rules.iter().map(String::as_str).collect::<Vec<_>>())?,
+        None => LogicalSolver::default(),
+    };
+    let puzzle = source.read()?;
+    let solution = solver.solve(&puzzle)?;
 
-            let idx = row * size + col;
-            let ch = chars[idx];
+    for (index, step) in solution.steps.iter().enumerate() {
+        println!("{:>3}. {step}", index + 1);
+    }
+    println!();
+    print_board(&solution.board);
+    if !solution.is_solved() {
+        return Err(format!(
+            "Stuck after {} steps: none of the rules apply, the rest needs guessing.",
+            solution.steps.len()
+        )
+        .into());
+    }
+    Ok(())
+}
 
-            if ch == '.' {
-                print!(". ");
-            } else {
-                print!("{} ", ch);
+fn print_board(board: &Board<SimpleCell>) {
+    for (row_index, row) in board.0.iter().enumerate() {
+        if row_index % 3 == 0 && row_index != 0 {
+            println!("------+-------+------");
+        }
+
+        for (col_index, cell) in row.iter().enumerate() {
+            if col_index % 3 == 0 && col_index != 0 {
+                print!("| ");
             }
+            print!("{} ", cell.value);
         }
         println!();
     }
 }
-
@@ -0,0 +1,66 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{constrained_cell::ConstrainedCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver, options::SolveOptions, propagation::Pipeline, search::BacktrackingSearch,
+        stats::SolveStats,
+    },
+};
+
+// Runs a pipeline of elimination rules on the board as a pre-process step, then backtracks over what is left,
+// skipping any values the rules ruled out. The default pipeline has naked and then hidden singles.
+pub struct BacktrackingWithPropagationSolver {
+    pipeline: Pipeline,
+}
+
+impl BacktrackingWithPropagationSolver {
+    pub fn new(pipeline: Pipeline) -> Self {
+        BacktrackingWithPropagationSolver { pipeline }
+    }
+
+    // Shared with the solvers that always use the same rules
+    pub(crate) fn search_with(
+        pipeline: &Pipeline,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<BacktrackingSearch<ConstrainedCell>, SudokuError> {
+        let has_entries = board.has_entries();
+        let mut board = board.with_entries_fixed().convert::<ConstrainedCell>();
+        let mut stats = SolveStats::default();
+
+        let start = Instant::now();
+        let propagated = pipeline.run(&mut board, &mut stats);
+        stats.preprocess_time = start.elapsed();
+        // Without entries the puzzle itself has no solution, so there is nothing to search
+        match propagated {
+            Err(contradiction) if has_entries => Err(SudokuError::Contradiction(contradiction)),
+            Err(_) => Ok(BacktrackingSearch::exhausted(board, stats, options)),
+            Ok(_) => Ok(BacktrackingSearch::new(board, stats, options)),
+        }
+    }
+}
+
+impl Default for BacktrackingWithPropagationSolver {
+    fn default() -> Self {
+        BacktrackingWithPropagationSolver::new(Pipeline::default())
+    }
+}
+
+impl Solver for BacktrackingWithPropagationSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(BacktrackingWithPropagationSolver::search_with(
+            &self.pipeline,
+            board,
+            options,
+        )?))
+    }
+}
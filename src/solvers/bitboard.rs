@@ -0,0 +1,242 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        stats::SolveStats,
+        units::{ALL_VALUES, BitGrid, unit_cells},
+    },
+};
+
+// Keeps the values used in each row, column and square as bit masks,
+// always guesses in the cell with the fewest candidates left,
+// and fills in every single it can find after each guess.
+pub struct BitboardSolver;
+
+impl Solver for BitboardSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(BitboardSearch::new(board, options)))
+    }
+}
+
+// The grid is small enough to copy whole for every guess, so undoing a guess is just dropping the copy
+impl BitGrid {
+    // None if two filled cells clash
+    fn from_board(board: &Board<SimpleCell>) -> Option<Self> {
+        let mut grid = BitGrid::new();
+        for (row, cells) in board.0.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if let CellValue::Filled(value) = cell.value() {
+                    let index = row * 9 + column;
+                    if (grid.candidates(index) >> value) & 1 == 0 {
+                        return None;
+                    }
+                    grid.set(index, *value as u8);
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    // Fills in naked singles (a cell with one candidate) and hidden singles
+    // (a value with one possible cell in a row, column or square) until there are none left.
+    // Returns the number of cells filled, or None if the board can no longer be solved.
+    fn propagate_singles(&mut self) -> Option<u64> {
+        let mut placed = 0;
+        loop {
+            let mut changed = false;
+            for index in 0..81 {
+                if self.cells[index] != 0 {
+                    continue;
+                }
+                let candidates = self.candidates(index);
+                if candidates == 0 {
+                    return None;
+                }
+                if candidates.count_ones() == 1 {
+                    self.set(index, candidates.trailing_zeros() as u8);
+                    placed += 1;
+                    changed = true;
+                }
+            }
+
+            for unit in 0..27 {
+                // Row major indexes of the unit's cells
+                let unit_indexes = || unit_cells(unit).map(|cell| cell.row as usize * 9 + cell.column as usize);
+                // Values that can go in at least one, and in at least two, of the unit's empty cells
+                let mut once = 0u16;
+                let mut twice = 0u16;
+                let mut used = 0u16;
+                for index in unit_indexes() {
+                    match self.cells[index] {
+                        0 => {
+                            let candidates = self.candidates(index);
+                            twice |= once & candidates;
+                            once |= candidates;
+                        }
+                        value => used |= 1 << value,
+                    }
+                }
+                if (once | used) != ALL_VALUES {
+                    return None;
+                }
+                let mut hidden = once & !twice;
+                while hidden != 0 {
+                    let value = hidden.trailing_zeros() as u8;
+                    hidden &= hidden - 1;
+                    let index = unit_indexes()
+                        .find(|&index| self.cells[index] == 0 && (self.candidates(index) >> value) & 1 == 1)?;
+                    self.set(index, value);
+                    placed += 1;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return Some(placed);
+            }
+        }
+    }
+
+    // The empty cell with the fewest candidates, None once the board is full
+    fn most_constrained_cell(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+        for index in 0..81 {
+            if self.cells[index] != 0 {
+                continue;
+            }
+            let candidates = self.candidates(index);
+            if best.is_none_or(|(_, best_candidates)| candidates.count_ones() < best_candidates.count_ones()) {
+                best = Some((index, candidates));
+                // After propagation no empty cell has fewer than 2
+                if candidates.count_ones() <= 2 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+}
+
+// One guessed cell, with the board from before the guess and the values still to try in it
+struct Guess {
+    state: BitGrid,
+    index: usize,
+    remaining: u16,
+}
+
+pub struct BitboardSearch {
+    board: Board<SimpleCell>,
+    guesses: Vec<Guess>,
+    // A board that is consistent so far, and still has to be checked for a solution or guessed in
+    pending: Option<BitGrid>,
+    solution: Option<BitGrid>,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl BitboardSearch {
+    pub fn new(board: &Board<SimpleCell>, options: &SolveOptions) -> Self {
+        let mut stats = SolveStats::default();
+        let start = Instant::now();
+        let pending = BitGrid::from_board(board).and_then(|mut state| {
+            stats.preprocessed_cells = state.propagate_singles()?;
+            Some(state)
+        });
+        stats.preprocess_time = start.elapsed();
+
+        BitboardSearch {
+            board: board.clone(),
+            guesses: Vec::with_capacity(81),
+            pending,
+            solution: None,
+            stats,
+            abort_check: AbortCheck::new(options),
+        }
+    }
+
+    fn find_next_solution(&mut self) -> bool {
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+
+            if let Some(state) = self.pending.take() {
+                match state.most_constrained_cell() {
+                    None => {
+                        // The next call moves on to the next guess on the stack
+                        self.solution = Some(state);
+                        return true;
+                    }
+                    Some((index, candidates)) => self.guesses.push(Guess {
+                        state,
+                        index,
+                        remaining: candidates,
+                    }),
+                }
+            }
+
+            let Some(guess) = self.guesses.last_mut() else {
+                return false;
+            };
+            if guess.remaining == 0 {
+                self.guesses.pop();
+                self.stats.backtracks += 1;
+                continue;
+            }
+            let value = guess.remaining.trailing_zeros() as u8;
+            guess.remaining &= guess.remaining - 1;
+
+            self.stats.guesses += 1;
+            let mut state = guess.state.clone();
+            state.set(guess.index, value);
+            if state.propagate_singles().is_some() {
+                self.pending = Some(state);
+            }
+        }
+    }
+}
+
+impl Iterator for BitboardSearch {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let found = self.find_next_solution();
+        self.stats.search_time += start.elapsed();
+        if !found {
+            return None;
+        }
+
+        let solved = self.solution.as_ref()?;
+        let mut solution = self.board.clone();
+        for (index, value) in solved.cells.iter().enumerate() {
+            let position = CellPosition {
+                row: (index / 9) as i8,
+                column: (index % 9) as i8,
+            };
+            solution.get_mut_cell(&position).value = CellValue::Filled(*value as i8);
+        }
+        Some(solution)
+    }
+}
+
+impl SolutionSearch for BitboardSearch {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
@@ -0,0 +1,303 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        stats::SolveStats,
+    },
+};
+
+// Sudoku as an exact cover problem, solved with Knuth's Algorithm X on dancing links.
+// Each of the 729 (row, column, digit) choices covers 4 of the 324 constraints:
+// the cell is filled, and the digit appears once in the row, the column and the square.
+pub struct DancingLinksSolver;
+
+impl Solver for DancingLinksSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(DancingLinksSearch::new(board, options)))
+    }
+}
+
+const CONSTRAINTS: usize = 324;
+const CHOICES: usize = 729;
+// Node 0 is the root, followed by one header per constraint
+const ROOT: usize = 0;
+
+// Which 4 constraints a (row, column, digit) choice covers, digit is 0 based
+fn choice_constraints(row: usize, column: usize, digit: usize) -> [usize; 4] {
+    let square = (row / 3) * 3 + column / 3;
+    [
+        row * 9 + column,
+        81 + row * 9 + digit,
+        162 + column * 9 + digit,
+        243 + square * 9 + digit,
+    ]
+}
+
+pub struct DancingLinksSearch {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    // Header node of the constraint each node belongs to
+    header: Vec<usize>,
+    // Choice each node belongs to, unused for headers
+    choice: Vec<usize>,
+    // Nodes left in each constraint's column, indexed by header node
+    size: Vec<usize>,
+    // The puzzle, with givens filled in
+    board: Board<SimpleCell>,
+    // One node per level of the search, from the choice currently being tried there
+    stack: Vec<usize>,
+    // The givens can't all be placed, so there is nothing to search
+    contradiction: bool,
+    // A solution was just returned, so the next call has to move past it first
+    resume: bool,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl DancingLinksSearch {
+    pub fn new(board: &Board<SimpleCell>, options: &SolveOptions) -> Self {
+        let start = Instant::now();
+        let node_count = 1 + CONSTRAINTS + CHOICES * 4;
+        let mut search = DancingLinksSearch {
+            left: Vec::with_capacity(node_count),
+            right: Vec::with_capacity(node_count),
+            up: Vec::with_capacity(node_count),
+            down: Vec::with_capacity(node_count),
+            header: Vec::with_capacity(node_count),
+            choice: Vec::with_capacity(node_count),
+            size: vec![0; 1 + CONSTRAINTS],
+            board: board.clone(),
+            stack: Vec::with_capacity(81),
+            contradiction: false,
+            resume: false,
+            stats: SolveStats::default(),
+            abort_check: AbortCheck::new(options),
+        };
+
+        // Headers, linked in a ring through the root
+        for node in 0..=CONSTRAINTS {
+            search.left.push(if node == 0 { CONSTRAINTS } else { node - 1 });
+            search.right.push(if node == CONSTRAINTS { 0 } else { node + 1 });
+            search.up.push(node);
+            search.down.push(node);
+            search.header.push(node);
+            search.choice.push(usize::MAX);
+        }
+
+        for choice in 0..CHOICES {
+            let (row, column, digit) = (choice / 81, (choice / 9) % 9, choice % 9);
+            let first = search.left.len();
+            for (offset, constraint) in choice_constraints(row, column, digit).into_iter().enumerate() {
+                let node = first + offset;
+                let header = constraint + 1;
+                // Append to the bottom of the column
+                search.up.push(search.up[header]);
+                search.down.push(header);
+                let above = search.up[header];
+                search.down[above] = node;
+                search.up[header] = node;
+                // and into the choice's own ring of 4
+                search.left.push(if offset == 0 { first + 3 } else { node - 1 });
+                search.right.push(if offset == 3 { first } else { node + 1 });
+                search.header.push(header);
+                search.choice.push(choice);
+                search.size[header] += 1;
+            }
+        }
+
+        // Givens are taken out of the matrix up front, rather than being searched over
+        let mut covered = [false; 1 + CONSTRAINTS];
+        'givens: for (row, cells) in board.0.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                let CellValue::Filled(value) = cell.value() else {
+                    continue;
+                };
+                let constraints = choice_constraints(row, column, *value as usize - 1);
+                if constraints.iter().any(|constraint| covered[constraint + 1]) {
+                    search.contradiction = true;
+                    break 'givens;
+                }
+                for constraint in constraints {
+                    covered[constraint + 1] = true;
+                    search.cover(constraint + 1);
+                }
+            }
+        }
+
+        search.stats.preprocess_time = start.elapsed();
+        search
+    }
+
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.header[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    // Exact reverse of cover, which is what lets the links dance back into place
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                self.size[self.header[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+
+    // Picks `row` at its level, covering the other constraints it satisfies
+    fn select(&mut self, row: usize) {
+        self.stats.guesses += 1;
+        self.stack.push(row);
+        let mut node = self.right[row];
+        while node != row {
+            self.cover(self.header[node]);
+            node = self.right[node];
+        }
+    }
+
+    fn deselect(&mut self, row: usize) {
+        let mut node = self.left[row];
+        while node != row {
+            self.uncover(self.header[node]);
+            node = self.left[node];
+        }
+    }
+
+    // The "fewest options" heuristic: the constraint that can be satisfied in the fewest ways
+    fn smallest_column(&self) -> usize {
+        let mut best = self.right[ROOT];
+        let mut header = self.right[best];
+        while header != ROOT && self.size[best] > 1 {
+            if self.size[header] < self.size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        best
+    }
+
+    // Undoes choices until one of them has an untried alternative, and moves on to it.
+    // Returns false once every choice at every level has been tried.
+    fn backtrack(&mut self) -> bool {
+        while let Some(row) = self.stack.pop() {
+            self.deselect(row);
+            let header = self.header[row];
+            let next = self.down[row];
+            if next != header {
+                self.select(next);
+                return true;
+            }
+            self.uncover(header);
+            self.stats.backtracks += 1;
+        }
+        false
+    }
+
+    fn find_next_solution(&mut self) -> bool {
+        if self.contradiction {
+            return false;
+        }
+        if self.resume {
+            self.resume = false;
+            if !self.backtrack() {
+                self.contradiction = true;
+                return false;
+            }
+        }
+
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+            // Every constraint is satisfied
+            if self.right[ROOT] == ROOT {
+                self.resume = true;
+                return true;
+            }
+
+            let header = self.smallest_column();
+            self.cover(header);
+            let row = self.down[header];
+            if row != header {
+                self.select(row);
+                continue;
+            }
+            // Nothing can satisfy this constraint
+            self.uncover(header);
+            self.stats.backtracks += 1;
+            if !self.backtrack() {
+                // Stays exhausted on later calls
+                self.contradiction = true;
+                return false;
+            }
+        }
+    }
+
+    fn solution(&self) -> Board<SimpleCell> {
+        let mut solution = self.board.clone();
+        for &row in &self.stack {
+            let choice = self.choice[row];
+            let position = CellPosition {
+                row: (choice / 81) as i8,
+                column: ((choice / 9) % 9) as i8,
+            };
+            solution.get_mut_cell(&position).value = CellValue::Filled((choice % 9) as i8 + 1);
+        }
+        solution
+    }
+}
+
+impl Iterator for DancingLinksSearch {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let found = self.find_next_solution();
+        self.stats.search_time += start.elapsed();
+        if found { Some(self.solution()) } else { None }
+    }
+}
+
+impl SolutionSearch for DancingLinksSearch {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
@@ -0,0 +1,253 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{
+            CellPosition, CellValue,
+            constrained_cell::{ConstrainedCell, ValueConstraint},
+            simple_cell::SimpleCell,
+        },
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        stats::SolveStats,
+        units::unit_cells,
+    },
+};
+
+// Backtracks over the cells in the same order as the other backtracking solvers,
+// but keeps every cell's constraints up to date while it does.
+// Each value it tries is removed from the cell's peers, and then any naked or hidden singles
+// that leaves are filled in, so dead ends show up as soon as some cell or value has nowhere to go.
+pub struct ForwardCheckingSolver;
+
+impl Solver for ForwardCheckingSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(ForwardCheckingSearch::new(board, options)))
+    }
+}
+
+// Every change the search makes to the board, so it can be undone in reverse
+enum TrailEntry {
+    Placed(CellPosition),
+    Eliminated(CellPosition, i8),
+}
+
+// A value tried in a cell, and how long the trail was before it
+struct Decision {
+    position: CellPosition,
+    value: i8,
+    trail_len: usize,
+}
+
+pub struct ForwardCheckingSearch {
+    board: Board<ConstrainedCell>,
+    trail: Vec<TrailEntry>,
+    decisions: Vec<Decision>,
+    // Set when the last board was a dead end or a solution, so the search has to move past it
+    backtracking: bool,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl ForwardCheckingSearch {
+    pub fn new(board: &Board<SimpleCell>, options: &SolveOptions) -> Self {
+        let start = Instant::now();
+        let mut search = ForwardCheckingSearch {
+            board: board.convert(),
+            trail: Vec::with_capacity(729),
+            decisions: Vec::with_capacity(81),
+            backtracking: false,
+            stats: SolveStats::default(),
+            abort_check: AbortCheck::new(options),
+        };
+
+        // Constraints from the givens are never undone
+        let mut consistent = search.board.is_correct();
+        for row in 0..9 {
+            for column in 0..9 {
+                let position = CellPosition { row, column };
+                let CellValue::Filled(value) = search.board.0[row as usize][column as usize].value else {
+                    continue;
+                };
+                for peer in position.peers() {
+                    search.board.get_mut_cell(&peer).value_constraint_map[value as usize] =
+                        ValueConstraint::FixedNotAllowed;
+                }
+            }
+        }
+        // Singles found before the first guess count as pre-processing, they are never undone either
+        if consistent {
+            consistent = search.propagate();
+            search.stats.preprocessed_cells = search
+                .trail
+                .iter()
+                .filter(|entry| matches!(entry, TrailEntry::Placed(_)))
+                .count() as u64;
+        }
+        search.backtracking = !consistent;
+        search.stats.preprocess_time = start.elapsed();
+        search
+    }
+
+    // Puts the value in the cell and removes it from the cell's empty peers.
+    // Returns false if that leaves a peer with no values at all.
+    fn assign(&mut self, position: CellPosition, value: i8) -> bool {
+        self.board.get_mut_cell(&position).value = CellValue::Filled(value);
+        self.trail.push(TrailEntry::Placed(position));
+
+        let mut consistent = true;
+        for peer in position.peers() {
+            let cell = self.board.get_mut_cell(&peer);
+            if cell.value != CellValue::Empty
+                || cell.value_constraint_map[value as usize] != ValueConstraint::Allowed
+            {
+                continue;
+            }
+            cell.value_constraint_map[value as usize] = ValueConstraint::SearchNotAllowed;
+            self.trail.push(TrailEntry::Eliminated(peer, value));
+            if cell.candidates() == 0 {
+                consistent = false;
+            }
+        }
+        consistent
+    }
+
+    // Fills in naked singles (a cell with one value left) and hidden singles
+    // (a value with one cell left in a row, column or square) until there are none.
+    // Returns false as soon as some cell or value has nowhere to go.
+    fn propagate(&mut self) -> bool {
+        loop {
+            let mut changed = false;
+            for row in 0..9 {
+                for column in 0..9 {
+                    let cell = &self.board.0[row as usize][column as usize];
+                    if cell.value != CellValue::Empty {
+                        continue;
+                    }
+                    let candidates = cell.candidates();
+                    if candidates == 0 {
+                        return false;
+                    }
+                    if candidates.count_ones() == 1
+                        && !self.assign(CellPosition { row, column }, candidates.trailing_zeros() as i8)
+                    {
+                        return false;
+                    }
+                    changed |= candidates.count_ones() == 1;
+                }
+            }
+
+            for unit in 0..27 {
+                for value in 1..=9 {
+                    let mut places = unit_cells(unit).filter(|position| {
+                        let cell = &self.board.0[position.row as usize][position.column as usize];
+                        cell.value == CellValue::Filled(value)
+                            || (cell.value == CellValue::Empty
+                                && cell.value_constraint_map[value as usize] == ValueConstraint::Allowed)
+                    });
+                    match (places.next(), places.next()) {
+                        (None, _) => return false,
+                        (Some(position), None)
+                            if self.board.0[position.row as usize][position.column as usize].value == CellValue::Empty =>
+                        {
+                            if !self.assign(position, value) {
+                                return false;
+                            }
+                            changed = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if !changed {
+                return true;
+            }
+        }
+    }
+
+    fn undo_to(&mut self, trail_len: usize) {
+        while self.trail.len() > trail_len {
+            match self.trail.pop() {
+                Some(TrailEntry::Placed(position)) => {
+                    self.board.get_mut_cell(&position).value = CellValue::Empty;
+                }
+                Some(TrailEntry::Eliminated(position, value)) => {
+                    self.board.get_mut_cell(&position).value_constraint_map[value as usize] =
+                        ValueConstraint::Allowed;
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn find_next_solution(&mut self) -> bool {
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+
+            if !self.backtracking {
+                let Some(position) = self.board.get_first_non_fixed_zero().map(|cell| cell.position) else {
+                    // Every cell is filled, and no assignment broke a constraint
+                    self.backtracking = true;
+                    return true;
+                };
+                self.decisions.push(Decision {
+                    position,
+                    value: 0,
+                    trail_len: self.trail.len(),
+                });
+            }
+
+            let Some(decision) = self.decisions.last() else {
+                return false;
+            };
+            let (position, tried, trail_len) = (decision.position, decision.value, decision.trail_len);
+            self.undo_to(trail_len);
+            let next_value = self.board.0[position.row as usize][position.column as usize]
+                .get_next_not_constrained(tried + 1);
+            let Some(value) = next_value else {
+                self.decisions.pop();
+                self.stats.backtracks += 1;
+                self.backtracking = true;
+                continue;
+            };
+            if let Some(decision) = self.decisions.last_mut() {
+                decision.value = value;
+            }
+
+            self.stats.guesses += 1;
+            self.backtracking = !(self.assign(position, value) && self.propagate());
+        }
+    }
+}
+
+impl Iterator for ForwardCheckingSearch {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let found = self.find_next_solution();
+        self.stats.search_time += start.elapsed();
+        if found { Some(self.board.convert()) } else { None }
+    }
+}
+
+impl SolutionSearch for ForwardCheckingSearch {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    board::{Board, cell::simple_cell::SimpleCell},
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        local_search::{LocalSearch, LocalSearchBoard, Strategy},
+        options::SolveOptions,
+        rng::Rng,
+    },
+};
+
+// Simulated annealing: tries a random swap each step, always keeps it if it doesn't add clashes,
+// and otherwise keeps it with probability e^(-added clashes / temperature).
+// The temperature starts at initial_temperature and is multiplied by cooling_rate every step.
+// If the best cost hasn't improved for reheat_after steps, the temperature goes back up to the start,
+// which shakes the board out of local minima.
+#[derive(Clone, Debug)]
+pub struct AnnealingSolver {
+    pub seed: u64,
+    pub initial_temperature: f64,
+    pub cooling_rate: f64,
+    pub reheat_after: u64,
+    pub max_steps: u64,
+}
+
+impl Default for AnnealingSolver {
+    fn default() -> Self {
+        AnnealingSolver {
+            seed: 0,
+            initial_temperature: 1.0,
+            cooling_rate: 0.9999,
+            reheat_after: 50_000,
+            max_steps: 10_000_000,
+        }
+    }
+}
+
+impl Solver for AnnealingSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        let annealing = Annealing {
+            settings: self.clone(),
+            temperature: self.initial_temperature,
+            best_cost: u32::MAX,
+            since_improvement: 0,
+        };
+        Ok(Box::new(LocalSearch::new(board, options, self.seed, self.max_steps, annealing)))
+    }
+
+    fn is_exhaustive(&self) -> bool {
+        false
+    }
+}
+
+struct Annealing {
+    settings: AnnealingSolver,
+    temperature: f64,
+    best_cost: u32,
+    since_improvement: u64,
+}
+
+impl Strategy for Annealing {
+    fn step(&mut self, board: &mut LocalSearchBoard, rng: &mut Rng) {
+        let (a, b) = board.swaps[rng.below(board.swaps.len())];
+        let delta = board.swap(a, b);
+        if delta > 0 && rng.next_f64() >= (-delta as f64 / self.temperature).exp() {
+            board.swap(a, b);
+        }
+
+        if board.cost() < self.best_cost {
+            self.best_cost = board.cost();
+            self.since_improvement = 0;
+        } else {
+            self.since_improvement += 1;
+        }
+        if self.since_improvement >= self.settings.reheat_after {
+            self.temperature = self.settings.initial_temperature;
+            self.best_cost = board.cost();
+            self.since_improvement = 0;
+        } else {
+            self.temperature *= self.settings.cooling_rate;
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    solvers::{
+        SolutionSearch,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        rng::Rng,
+        stats::SolveStats,
+    },
+};
+
+pub mod annealing;
+pub mod tabu;
+
+// A full board where every square holds each digit once, so only rows and columns can clash.
+// Swapping two non fixed cells of the same square keeps it that way.
+pub struct LocalSearchBoard {
+    // Row major
+    cells: [u8; 81],
+    row_counts: [[u8; 10]; 9],
+    col_counts: [[u8; 10]; 9],
+    // Digits missing from each row and column, summed up. 0 means the board is solved.
+    cost: u32,
+    // Pairs of non fixed cells that share a square, the moves both searches pick from
+    pub swaps: Vec<(usize, usize)>,
+}
+
+// Digits missing from a row or column, which is also how many of its cells are duplicates
+fn line_cost(counts: &[u8; 10]) -> u32 {
+    counts[1..].iter().filter(|&&count| count == 0).count() as u32
+}
+
+impl LocalSearchBoard {
+    // Fills each square's empty cells with a random order of its missing digits.
+    // None if the givens already clash.
+    pub fn new(board: &Board<SimpleCell>, rng: &mut Rng) -> Option<Self> {
+        if !board.is_correct() {
+            return None;
+        }
+        let mut cells = [0u8; 81];
+        let mut fixed = [false; 81];
+        for (index, cell) in board.0.iter().flatten().enumerate() {
+            if let CellValue::Filled(value) = cell.value() {
+                cells[index] = *value as u8;
+                fixed[index] = true;
+            }
+        }
+
+        let mut swaps = Vec::new();
+        for square in 0..9 {
+            let square_cells: Vec<usize> = (0..9)
+                .map(|i| ((square / 3) * 3 + i / 3) * 9 + (square % 3) * 3 + i % 3)
+                .collect();
+            let mut missing: Vec<u8> = (1..=9)
+                .filter(|value| !square_cells.iter().any(|&index| cells[index] == *value))
+                .collect();
+            rng.shuffle(&mut missing);
+            let free: Vec<usize> = square_cells.into_iter().filter(|&index| !fixed[index]).collect();
+            for (&index, value) in free.iter().zip(missing) {
+                cells[index] = value;
+            }
+            for (i, &a) in free.iter().enumerate() {
+                for &b in &free[i + 1..] {
+                    swaps.push((a, b));
+                }
+            }
+        }
+
+        let mut row_counts = [[0u8; 10]; 9];
+        let mut col_counts = [[0u8; 10]; 9];
+        for (index, &value) in cells.iter().enumerate() {
+            row_counts[index / 9][value as usize] += 1;
+            col_counts[index % 9][value as usize] += 1;
+        }
+        let cost = row_counts.iter().chain(col_counts.iter()).map(line_cost).sum();
+
+        Some(LocalSearchBoard {
+            cells,
+            row_counts,
+            col_counts,
+            cost,
+            swaps,
+        })
+    }
+
+    pub fn cost(&self) -> u32 {
+        self.cost
+    }
+
+    // Swaps the values of two cells in the same square, returning the change in cost.
+    // Swapping the same pair again undoes it.
+    pub fn swap(&mut self, a: usize, b: usize) -> i32 {
+        let (row_a, row_b, col_a, col_b) = (a / 9, b / 9, a % 9, b % 9);
+        let lines_cost = |board: &LocalSearchBoard| {
+            let mut cost = line_cost(&board.row_counts[row_a]) + line_cost(&board.col_counts[col_a]);
+            if row_b != row_a {
+                cost += line_cost(&board.row_counts[row_b]);
+            }
+            if col_b != col_a {
+                cost += line_cost(&board.col_counts[col_b]);
+            }
+            cost as i32
+        };
+
+        let before = lines_cost(self);
+        let (value_a, value_b) = (self.cells[a] as usize, self.cells[b] as usize);
+        self.row_counts[row_a][value_a] -= 1;
+        self.row_counts[row_a][value_b] += 1;
+        self.row_counts[row_b][value_b] -= 1;
+        self.row_counts[row_b][value_a] += 1;
+        self.col_counts[col_a][value_a] -= 1;
+        self.col_counts[col_a][value_b] += 1;
+        self.col_counts[col_b][value_b] -= 1;
+        self.col_counts[col_b][value_a] += 1;
+        self.cells.swap(a, b);
+
+        let delta = lines_cost(self) - before;
+        self.cost = (self.cost as i32 + delta) as u32;
+        delta
+    }
+}
+
+// What sets the local searches apart: how they pick and accept the next swap
+pub trait Strategy {
+    fn step(&mut self, board: &mut LocalSearchBoard, rng: &mut Rng);
+}
+
+// Runs a strategy until the board has no clashes left or the step budget runs out.
+// It can't tell an unsolvable puzzle from one it hasn't solved yet,
+// and it stops after the first solution it finds.
+pub struct LocalSearch<S>
+where
+    S: Strategy,
+{
+    strategy: S,
+    // None when the givens clash
+    board: Option<LocalSearchBoard>,
+    puzzle: Board<SimpleCell>,
+    rng: Rng,
+    max_steps: u64,
+    done: bool,
+    aborted: Option<AbortReason>,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl<S> LocalSearch<S>
+where
+    S: Strategy,
+{
+    pub fn new(puzzle: &Board<SimpleCell>, options: &SolveOptions, seed: u64, max_steps: u64, strategy: S) -> Self {
+        let start = Instant::now();
+        let mut rng = Rng::new(seed);
+        let board = LocalSearchBoard::new(puzzle, &mut rng);
+        let stats = SolveStats {
+            preprocess_time: start.elapsed(),
+            ..SolveStats::default()
+        };
+
+        LocalSearch {
+            strategy,
+            board,
+            puzzle: puzzle.clone(),
+            rng,
+            max_steps,
+            done: false,
+            aborted: None,
+            stats,
+            abort_check: AbortCheck::new(options),
+        }
+    }
+
+    // Steps count as guesses, so the node budget in SolveOptions applies to them too
+    fn find_solution(&mut self) -> bool {
+        let Some(board) = self.board.as_mut() else {
+            return false;
+        };
+        // Every square is down to one free cell, so there is nothing left to swap
+        if board.swaps.is_empty() {
+            return board.cost() == 0;
+        }
+        while board.cost() > 0 {
+            if let Some(reason) = self.abort_check.check(self.stats.guesses) {
+                self.aborted = Some(reason);
+                return false;
+            }
+            if self.stats.guesses >= self.max_steps {
+                self.aborted = Some(AbortReason::StepBudget);
+                return false;
+            }
+            self.strategy.step(board, &mut self.rng);
+            self.stats.guesses += 1;
+        }
+        true
+    }
+}
+
+impl<S> Iterator for LocalSearch<S>
+where
+    S: Strategy,
+{
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.done = true;
+
+        let start = Instant::now();
+        let found = self.find_solution();
+        self.stats.search_time += start.elapsed();
+        if !found {
+            return None;
+        }
+
+        let board = self.board.as_ref()?;
+        let mut solution = self.puzzle.clone();
+        for (index, value) in board.cells.iter().enumerate() {
+            let position = CellPosition {
+                row: (index / 9) as i8,
+                column: (index % 9) as i8,
+            };
+            solution.get_mut_cell(&position).value = CellValue::Filled(*value as i8);
+        }
+        Some(solution)
+    }
+}
+
+impl<S> SolutionSearch for LocalSearch<S>
+where
+    S: Strategy + Send,
+{
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.aborted
+    }
+}
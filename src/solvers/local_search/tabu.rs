@@ -0,0 +1,100 @@
+use crate::{
+    board::{Board, cell::simple_cell::SimpleCell},
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        local_search::{LocalSearch, LocalSearchBoard, Strategy},
+        options::SolveOptions,
+        rng::Rng,
+    },
+};
+
+// Tabu search: every step makes the best swap available, even if it adds clashes,
+// with ties broken at random. A pair that was just swapped is tabu for the next `tenure` steps,
+// so the search can't undo its last few moves and circle around the same local minimum.
+// A tabu swap is still allowed if it would give the best board seen so far.
+#[derive(Clone, Debug)]
+pub struct TabuSolver {
+    pub seed: u64,
+    pub tenure: u64,
+    pub max_steps: u64,
+}
+
+impl Default for TabuSolver {
+    fn default() -> Self {
+        TabuSolver {
+            seed: 0,
+            tenure: 10,
+            max_steps: 1_000_000,
+        }
+    }
+}
+
+impl Solver for TabuSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        let tabu = Tabu {
+            tenure: self.tenure,
+            step: 0,
+            tabu_until: Vec::new(),
+            best_cost: u32::MAX,
+        };
+        Ok(Box::new(LocalSearch::new(board, options, self.seed, self.max_steps, tabu)))
+    }
+
+    fn is_exhaustive(&self) -> bool {
+        false
+    }
+}
+
+struct Tabu {
+    tenure: u64,
+    step: u64,
+    // The step each swap (by its index in LocalSearchBoard::swaps) can be made again from
+    tabu_until: Vec<u64>,
+    best_cost: u32,
+}
+
+impl Strategy for Tabu {
+    fn step(&mut self, board: &mut LocalSearchBoard, rng: &mut Rng) {
+        if self.tabu_until.len() != board.swaps.len() {
+            self.tabu_until = vec![0; board.swaps.len()];
+        }
+        self.best_cost = self.best_cost.min(board.cost());
+
+        let mut best: Option<(usize, i32)> = None;
+        let mut ties = 0;
+        for index in 0..board.swaps.len() {
+            let (a, b) = board.swaps[index];
+            let delta = board.swap(a, b);
+            board.swap(a, b);
+            let aspiration = ((board.cost() as i32 + delta) as u32) < self.best_cost;
+            if self.tabu_until[index] > self.step && !aspiration {
+                continue;
+            }
+            match best {
+                Some((_, best_delta)) if delta > best_delta => {}
+                Some((_, best_delta)) if delta == best_delta => {
+                    ties += 1;
+                    if rng.below(ties) == 0 {
+                        best = Some((index, delta));
+                    }
+                }
+                _ => {
+                    best = Some((index, delta));
+                    ties = 1;
+                }
+            }
+        }
+
+        if let Some((index, _)) = best {
+            let (a, b) = board.swaps[index];
+            board.swap(a, b);
+            self.tabu_until[index] = self.step + self.tenure + 1;
+        }
+        self.step += 1;
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    board::{
+        Board,
+        cell::{CellValue, constrained_cell::ConstrainedCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::propagation::{Propagator, RULE_NAMES, Step, get_rule},
+};
+
+// Solves the way people do by hand: one technique at a time, always the first of its rules that applies,
+// and never a guess. Every step is recorded so the solution can be explained.
+// Once none of the rules apply any more it stops, even if the board isn't full.
+pub struct LogicalSolver {
+    rules: Vec<Box<dyn Propagator>>,
+}
+
+// How far logic got, and how
+pub struct LogicalSolution {
+    pub steps: Vec<Step>,
+    // The board after the last step, with every cell filled in if the rules were enough
+    pub board: Board<SimpleCell>,
+}
+
+impl LogicalSolution {
+    // False if the rules ran out, the rest of the board would need guessing
+    pub fn is_solved(&self) -> bool {
+        self.board.0.iter().flatten().all(|cell| cell.value != CellValue::Empty)
+    }
+}
+
+impl LogicalSolver {
+    // The rules are tried in order for every step, so the simplest ones should go first
+    pub fn new(rules: Vec<Box<dyn Propagator>>) -> Self {
+        LogicalSolver { rules }
+    }
+
+    // Takes the same names as get_rule
+    pub fn from_names(names: &[&str]) -> Result<Self, SudokuError> {
+        Ok(LogicalSolver::new(
+            names.iter().map(|name| get_rule(name)).collect::<Result<_, _>>()?,
+        ))
+    }
+
+    pub fn solve(&self, puzzle: &str) -> Result<LogicalSolution, SudokuError> {
+        self.solve_board(&Board::<SimpleCell>::parse_puzzle_string(puzzle)?)
+    }
+
+    // A player's entries are taken as given.
+    // If the board contradicts itself, the error says where, instead of the steps.
+    pub fn solve_board(&self, board: &Board<SimpleCell>) -> Result<LogicalSolution, SudokuError> {
+        let mut board = board.with_entries_fixed().convert::<ConstrainedCell>();
+        if let Some(clash) = board.find_clash() {
+            return Err(SudokuError::Contradiction(clash));
+        }
+
+        let mut steps = Vec::new();
+        'steps: loop {
+            for rule in &self.rules {
+                if let Some(step) = rule.next_step(&board).map_err(SudokuError::Contradiction)? {
+                    step.apply(&mut board);
+                    steps.push(step);
+                    continue 'steps;
+                }
+            }
+            break;
+        }
+
+        Ok(LogicalSolution {
+            steps,
+            board: board.convert(),
+        })
+    }
+}
+
+// Every rule, simplest first
+impl Default for LogicalSolver {
+    fn default() -> Self {
+        LogicalSolver::from_names(&RULE_NAMES).expect("RULE_NAMES are all known rules")
+    }
+}
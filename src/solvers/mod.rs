@@ -1,28 +1,193 @@
-use crate::solvers::{backtracking::BacktrackingSolver, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver};
+use crate::board::{Board, cell::simple_cell::SimpleCell};
+use crate::error::SudokuError;
+use crate::solvers::options::{AbortReason, SolveOptions};
+use crate::solvers::stats::SolveStats;
+use crate::solvers::{backtracking::BacktrackingSolver, backtracking_with_propagation::BacktrackingWithPropagationSolver, bitboard::BitboardSolver, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, dancing_links::DancingLinksSolver, forward_checking::ForwardCheckingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver, local_search::{annealing::AnnealingSolver, tabu::TabuSolver}, portfolio::PortfolioSolver, sat::SatSolver, trail::TrailSolver};
 
 #[cfg(test)]
 mod tests;
 
 
 pub mod backtracking;
+pub mod backtracking_with_propagation;
+pub mod bitboard;
 pub mod cell_eliminated_backtracking;
+pub mod dancing_links;
+pub mod forward_checking;
 pub mod group_eliminated_backtracking;
+pub mod local_search;
+pub mod logical;
+pub mod options;
+pub mod portfolio;
+pub mod propagation;
+pub mod rng;
+pub mod sat;
+pub mod search;
+pub mod stats;
+pub mod strategy;
+pub mod trail;
+mod units;
 
-pub trait Solver {
+// Only one of these is made per solve, so boxing the boards isn't worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveOutcome {
+    Solved(Board<SimpleCell>),
+    Unsolvable,
+    // The puzzle is not well formed, these are two of its solutions
+    MultipleSolutions {
+        first: Board<SimpleCell>,
+        second: Board<SimpleCell>,
+    },
+    // The solver hit one of the limits in its SolveOptions before finishing
+    Aborted(AbortReason),
+}
+
+impl SolveOutcome {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, SolveOutcome::Solved(_))
+    }
+
+    pub fn solution(&self) -> Option<&Board<SimpleCell>> {
+        match self {
+            SolveOutcome::Solved(board) => Some(board),
+            _ => None,
+        }
+    }
+}
+
+// Solutions of a single puzzle, found lazily
+pub type Solutions<'a> = Box<dyn Iterator<Item = Board<SimpleCell>> + 'a>;
+
+// A running search for the solutions of one board.
+// Send so the portfolio solver can hand it to a thread of its own.
+pub trait SolutionSearch: Iterator<Item = Board<SimpleCell>> + Send {
+    // Work done so far, including any pre-processing
+    fn stats(&self) -> &SolveStats;
+    // Set once the search stopped early because of its SolveOptions
+    fn aborted(&self) -> Option<AbortReason>;
+}
+
+// Send + Sync so one solver can be shared between the threads of a parallel batch
+pub trait Solver: Send + Sync {
     #[allow(dead_code)]
     fn name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
-    fn solve(&self, board: &'static str) -> Result<(bool, String), &'static str>;
+
+    // False for solvers that stop at the first solution, which can't count or check uniqueness.
+    // Their searches also can't tell an unsolvable puzzle apart from one they gave up on.
+    fn is_exhaustive(&self) -> bool {
+        true
+    }
+
+    // Starts searching the board, each solution is only found when it is asked for
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError>;
+
+    fn board_solutions<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+    ) -> Result<Solutions<'a>, SudokuError> {
+        Ok(self.search(board, &SolveOptions::default())?)
+    }
+
+    fn solutions<'a>(
+        &'a self,
+        puzzle: &str,
+    ) -> Result<Solutions<'a>, SudokuError> {
+        self.board_solutions(&Board::<SimpleCell>::parse_puzzle_string(puzzle)?)
+    }
+
+    // Stats are returned for aborted solves too, covering the work done before giving up
+    fn solve_board_with(
+        &self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<(SolveOutcome, SolveStats), SudokuError> {
+        let mut search = self.search(board, options)?;
+        let outcome = match (search.next(), search.aborted()) {
+            (Some(solution), _) => SolveOutcome::Solved(solution),
+            (None, Some(reason)) => SolveOutcome::Aborted(reason),
+            (None, None) => SolveOutcome::Unsolvable,
+        };
+        Ok((outcome, search.stats().clone()))
+    }
+
+    fn solve_with(
+        &self,
+        puzzle: &str,
+        options: &SolveOptions,
+    ) -> Result<(SolveOutcome, SolveStats), SudokuError> {
+        self.solve_board_with(&Board::<SimpleCell>::parse_puzzle_string(puzzle)?, options)
+    }
+
+    fn solve_board_with_stats(
+        &self,
+        board: &Board<SimpleCell>,
+    ) -> Result<(SolveOutcome, SolveStats), SudokuError> {
+        self.solve_board_with(board, &SolveOptions::default())
+    }
+
+    fn solve_with_stats(&self, puzzle: &str) -> Result<(SolveOutcome, SolveStats), SudokuError> {
+        self.solve_board_with_stats(&Board::<SimpleCell>::parse_puzzle_string(puzzle)?)
+    }
+
+    fn solve_board(&self, board: &Board<SimpleCell>) -> Result<SolveOutcome, SudokuError> {
+        Ok(self.solve_board_with_stats(board)?.0)
+    }
+
+    fn solve(&self, puzzle: &str) -> Result<SolveOutcome, SudokuError> {
+        self.solve_board(&Board::<SimpleCell>::parse_puzzle_string(puzzle)?)
+    }
+
+    // Counts solutions, but stops searching once `limit` have been found
+    fn count_solutions(&self, puzzle: &str, limit: usize) -> Result<usize, SudokuError> {
+        if !self.is_exhaustive() {
+            return Err(SudokuError::NotExhaustive(self.name()));
+        }
+        Ok(self.solutions(puzzle)?.take(limit).count())
+    }
+
+    fn has_unique_solution(&self, puzzle: &str) -> Result<bool, SudokuError> {
+        Ok(self.count_solutions(puzzle, 2)? == 1)
+    }
+
+    // Like solve, but keeps searching for a second solution to tell if the puzzle is well formed
+    fn solve_unique(&self, puzzle: &str) -> Result<SolveOutcome, SudokuError> {
+        if !self.is_exhaustive() {
+            return Err(SudokuError::NotExhaustive(self.name()));
+        }
+        let mut solutions = self.solutions(puzzle)?;
+        Ok(match (solutions.next(), solutions.next()) {
+            (None, _) => SolveOutcome::Unsolvable,
+            (Some(first), None) => SolveOutcome::Solved(first),
+            (Some(first), Some(second)) => SolveOutcome::MultipleSolutions { first, second },
+        })
+    }
 }
 
+// Every name accepted by get_solver
+pub const SOLVER_NAMES: [&str; 12] = ["backtracking", "celleliminated", "groupeliminated", "propagation", "trail", "forwardchecking", "dlx", "bitboard", "sat", "annealing", "tabu", "portfolio"];
 
-pub fn get_solver(s: &str) -> Result<Box<dyn Solver>, String> {
+pub fn get_solver(s: &str) -> Result<Box<dyn Solver>, SudokuError> {
     match s.to_lowercase().as_str() {
         "backtracking" => Ok(Box::new(BacktrackingSolver)),
         "celleliminated" => Ok(Box::new(CellEliminatedBacktrackingSolver)),
         "groupeliminated" => Ok(Box::new(GroupEliminatedBacktrackingSolver)),
-        _ => Err("Unknown solver type: ".to_owned() + s)
+        "propagation" => Ok(Box::new(BacktrackingWithPropagationSolver::default())),
+        "trail" => Ok(Box::new(TrailSolver)),
+        "forwardchecking" => Ok(Box::new(ForwardCheckingSolver)),
+        "dlx" => Ok(Box::new(DancingLinksSolver)),
+        "bitboard" => Ok(Box::new(BitboardSolver)),
+        "sat" => Ok(Box::new(SatSolver)),
+        "annealing" => Ok(Box::new(AnnealingSolver::default())),
+        "tabu" => Ok(Box::new(TabuSolver::default())),
+        "portfolio" => Ok(Box::new(PortfolioSolver::default())),
+        _ => Err(SudokuError::UnknownSolver(s.to_owned()))
      }
 }
 
@@ -0,0 +1,105 @@
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::solvers::strategy::SearchStrategy;
+
+// Limits on how long a solve may run, and how the search is ordered. The default has no limits.
+#[derive(Clone, Debug, Default)]
+pub struct SolveOptions {
+    // Wall-clock time allowed, measured from when the search starts
+    pub timeout: Option<Duration>,
+    // Number of guesses the search may make
+    pub node_budget: Option<u64>,
+    // Set to true (from any thread) to stop the search
+    pub cancel: Option<Arc<AtomicBool>>,
+    // Cell and value ordering for the backtracking solvers, the others ignore it
+    pub strategy: SearchStrategy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbortReason {
+    Timeout,
+    NodeBudget,
+    Cancelled,
+    // A local search used up its own step budget without finding a solution
+    StepBudget,
+}
+
+impl fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AbortReason::Timeout => "ran out of time",
+                AbortReason::NodeBudget => "ran out of guesses",
+                AbortReason::Cancelled => "cancelled",
+                AbortReason::StepBudget => "ran out of steps",
+            }
+        )
+    }
+}
+
+// Reading the clock on every guess is slower than the guess itself
+const CLOCK_CHECK_INTERVAL: u32 = 1024;
+
+// The running side of SolveOptions, which solvers poll from their search loops
+#[derive(Clone, Debug)]
+pub struct AbortCheck {
+    deadline: Option<Instant>,
+    node_budget: Option<u64>,
+    cancel: Option<Arc<AtomicBool>>,
+    until_clock_check: u32,
+    aborted: Option<AbortReason>,
+}
+
+impl AbortCheck {
+    pub fn new(options: &SolveOptions) -> Self {
+        AbortCheck {
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+            node_budget: options.node_budget,
+            cancel: options.cancel.clone(),
+            until_clock_check: 0,
+            aborted: None,
+        }
+    }
+
+    // Call once per search step with the number of guesses made so far.
+    // Once this returns a reason it keeps returning it.
+    pub fn check(&mut self, nodes: u64) -> Option<AbortReason> {
+        if self.aborted.is_some() {
+            return self.aborted;
+        }
+        if self.node_budget.is_some_and(|budget| nodes > budget) {
+            self.aborted = Some(AbortReason::NodeBudget);
+            return self.aborted;
+        }
+
+        if self.until_clock_check > 0 {
+            self.until_clock_check -= 1;
+            return None;
+        }
+        self.until_clock_check = CLOCK_CHECK_INTERVAL;
+
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            self.aborted = Some(AbortReason::Cancelled);
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.aborted = Some(AbortReason::Timeout);
+        }
+        self.aborted
+    }
+
+    pub fn aborted(&self) -> Option<AbortReason> {
+        self.aborted
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    board::{Board, cell::simple_cell::SimpleCell},
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver, bitboard::BitboardSolver,
+        cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, dancing_links::DancingLinksSolver,
+        get_solver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver,
+        options::{AbortReason, SolveOptions},
+        stats::SolveStats,
+    },
+};
+
+// Races several solvers on their own threads and takes the first answer, either a solution
+// or a proof there is none. The rest are then cancelled.
+// It only ever finds one solution, so it can't count them.
+pub struct PortfolioSolver {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl PortfolioSolver {
+    pub fn new(solvers: Vec<Box<dyn Solver>>) -> Self {
+        PortfolioSolver { solvers }
+    }
+
+    // Takes the same names as get_solver
+    pub fn from_names(names: &[&str]) -> Result<Self, SudokuError> {
+        Ok(PortfolioSolver::new(
+            names.iter().map(|name| get_solver(name)).collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+// The two eliminated solvers each win one of the benchmarks, the other two are the fastest overall
+impl Default for PortfolioSolver {
+    fn default() -> Self {
+        PortfolioSolver::new(vec![
+            Box::new(CellEliminatedBacktrackingSolver),
+            Box::new(GroupEliminatedBacktrackingSolver),
+            Box::new(DancingLinksSolver),
+            Box::new(BitboardSolver),
+        ])
+    }
+}
+
+impl Solver for PortfolioSolver {
+    fn is_exhaustive(&self) -> bool {
+        false
+    }
+
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let member_options = SolveOptions {
+            cancel: Some(cancel.clone()),
+            ..options.clone()
+        };
+        // Started up front, so a board one of them rejects is an error here rather than a solver that never answers
+        let searches = self
+            .solvers
+            .iter()
+            .map(|solver| Ok((solver.name(), solver.search(board, &member_options)?)))
+            .collect::<Result<_, SudokuError>>()?;
+        Ok(Box::new(PortfolioSearch {
+            searches,
+            cancel,
+            options: options.clone(),
+            aborted: None,
+            stats: SolveStats::default(),
+        }))
+    }
+}
+
+// How often the race checks the caller's own cancel flag
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+struct Finished {
+    solver: &'static str,
+    solution: Option<Board<SimpleCell>>,
+    aborted: Option<AbortReason>,
+    stats: SolveStats,
+}
+
+pub struct PortfolioSearch<'a> {
+    // Taken by the race, so empty once it has run
+    searches: Vec<(&'static str, Box<dyn SolutionSearch + 'a>)>,
+    // Shared with every search, set once one of them has an answer
+    cancel: Arc<AtomicBool>,
+    // The caller's options, for their own cancel flag
+    options: SolveOptions,
+    aborted: Option<AbortReason>,
+    stats: SolveStats,
+}
+
+impl PortfolioSearch<'_> {
+    // Runs every solver until one of them has an answer, then cancels the rest and waits for them
+    fn race(&mut self) -> Option<Board<SimpleCell>> {
+        let cancel = self.cancel.clone();
+        let (sender, receiver) = mpsc::channel::<Finished>();
+        let searches = std::mem::take(&mut self.searches);
+
+        thread::scope(|scope| {
+            for (solver, mut search) in searches {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let solution = search.next();
+                    // The receiver is gone once a winner is found, the result isn't needed then
+                    let _ = sender.send(Finished {
+                        solver,
+                        solution,
+                        aborted: search.aborted(),
+                        stats: search.stats().clone(),
+                    });
+                });
+            }
+            drop(sender);
+
+            let mut first_abort: Option<Finished> = None;
+            loop {
+                match receiver.recv_timeout(CANCEL_POLL_INTERVAL) {
+                    // A solution, or a search that ran to the end without one
+                    Ok(finished) if finished.solution.is_some() || finished.aborted.is_none() => {
+                        cancel.store(true, Ordering::Relaxed);
+                        self.stats = SolveStats {
+                            winner: Some(finished.solver),
+                            ..finished.stats
+                        };
+                        return finished.solution;
+                    }
+                    Ok(finished) => {
+                        first_abort.get_or_insert(finished);
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if self
+                            .options
+                            .cancel
+                            .as_ref()
+                            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+                        {
+                            cancel.store(true, Ordering::Relaxed);
+                        }
+                    }
+                    // Every solver gave up
+                    Err(RecvTimeoutError::Disconnected) => {
+                        if let Some(finished) = first_abort {
+                            self.aborted = finished.aborted;
+                            self.stats = finished.stats;
+                        }
+                        return None;
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Iterator for PortfolioSearch<'_> {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.searches.is_empty() {
+            return None;
+        }
+        self.race()
+    }
+}
+
+impl SolutionSearch for PortfolioSearch<'_> {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.aborted
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::{
+    board::{
+        Board, Group,
+        cell::{CellPosition, constrained_cell::ConstrainedCell},
+    },
+    error::Contradiction,
+    solvers::propagation::{Propagator, Step, candidate_grid, picked, subsets},
+};
+
+// If a digit can only go in the same n columns in each of n rows (the base set), those rows put one copy of it in
+// each of the n columns (the cover set), so no other cell of those columns can take it. The same works with rows and
+// columns swapped. X-Wings have 2 lines, Swordfish 3 and Jellyfish 4.
+//
+// A finned fish is a fish with a few extra candidates, the fins, all in one square. Either a fin is right or the
+// fish holds, so the cells that both the fish and every fin rule out can't take the digit: the ones in the cover
+// set and the fins' square. It is called sashimi when some base line has only one candidate left in the cover set.
+pub struct Fish {
+    size: u32,
+    finned: bool,
+}
+
+// The lines a fish was found on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FishSets {
+    pub digit: i8,
+    pub base: Vec<Group>,
+    pub cover: Vec<Group>,
+    pub fins: Vec<CellPosition>,
+}
+
+impl Fish {
+    pub const X_WING: Fish = Fish { size: 2, finned: false };
+    pub const SWORDFISH: Fish = Fish { size: 3, finned: false };
+    pub const JELLYFISH: Fish = Fish { size: 4, finned: false };
+    // These also find the sashimi versions
+    pub const FINNED_X_WING: Fish = Fish { size: 2, finned: true };
+    pub const FINNED_SWORDFISH: Fish = Fish { size: 3, finned: true };
+    pub const FINNED_JELLYFISH: Fish = Fish { size: 4, finned: true };
+
+    fn technique(&self, sashimi: bool) -> &'static str {
+        match (self.size, self.finned, sashimi) {
+            (2, false, _) => "X-Wing",
+            (3, false, _) => "Swordfish",
+            (_, false, _) => "Jellyfish",
+            (2, true, false) => "Finned X-Wing",
+            (3, true, false) => "Finned Swordfish",
+            (_, true, false) => "Finned Jellyfish",
+            (2, true, true) => "Sashimi X-Wing",
+            (3, true, true) => "Sashimi Swordfish",
+            (_, true, true) => "Sashimi Jellyfish",
+        }
+    }
+
+    // Looks for a fish on the digit with rows as the base set, or columns if rows_first is false
+    fn find(&self, candidates: &[[u16; 9]; 9], digit: i8, rows_first: bool) -> Option<Step> {
+        let line = |index: usize| if rows_first { Group::Row(index as i8) } else { Group::Column(index as i8) };
+        let crossing = |index: usize| if rows_first { Group::Column(index as i8) } else { Group::Row(index as i8) };
+        let has = |cell: CellPosition| (candidates[cell.row as usize][cell.column as usize] >> digit) & 1u16 == 1;
+
+        // For each line, a bit for every crossing line it can have the digit in
+        let mut spots = [0u16; 9];
+        for (index, spot) in spots.iter_mut().enumerate() {
+            for (crossing, cell) in line(index).cells().enumerate() {
+                if has(cell) {
+                    *spot |= 1u16 << crossing;
+                }
+            }
+        }
+        // Lines with a single spot are hidden singles, unless a fin can make up the rest
+        let lines: Vec<usize> = (0..9)
+            .filter(|&index| match spots[index].count_ones() {
+                0 => false,
+                1 => self.finned,
+                count => self.finned || count <= self.size,
+            })
+            .collect();
+
+        for chosen in subsets(lines.len(), self.size) {
+            let base: Vec<usize> = picked(&lines, chosen).collect();
+            let all = base.iter().fold(0u16, |all, &index| all | spots[index]);
+            let covers: Vec<u16> = if self.finned {
+                // Every way to leave some of the spots out as fins
+                subsets(9, self.size).filter(|&cover| cover & !all == 0 && all & !cover != 0).collect()
+            } else if all.count_ones() == self.size {
+                vec![all]
+            } else {
+                Vec::new()
+            };
+
+            for cover in covers {
+                if base.iter().any(|&index| spots[index] & cover == 0) {
+                    continue;
+                }
+                let fins: Vec<CellPosition> = base
+                    .iter()
+                    .flat_map(|&index| {
+                        line(index)
+                            .cells()
+                            .enumerate()
+                            .filter(move |&(crossing, _)| ((spots[index] & !cover) >> crossing) & 1u16 == 1)
+                            .map(|(_, cell)| cell)
+                    })
+                    .collect();
+                let fin_square = fins.first().map(|&fin| Group::containing(fin)[2]);
+                if fins.iter().any(|&fin| Some(Group::containing(fin)[2]) != fin_square) {
+                    continue;
+                }
+
+                let cover_lines: Vec<Group> = (0..9).filter(|index| (cover >> index) & 1u16 == 1).map(crossing).collect();
+                let eliminations: Vec<(CellPosition, i8)> = cover_lines
+                    .iter()
+                    .flat_map(|cover_line| cover_line.cells().enumerate())
+                    .filter(|&(index, cell)| {
+                        !base.contains(&index)
+                            && has(cell)
+                            && fin_square.is_none_or(|square| Group::containing(cell)[2] == square)
+                    })
+                    .map(|(_, cell)| (cell, digit))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                let sashimi = self.finned && base.iter().any(|&index| (spots[index] & cover).count_ones() == 1);
+                return Some(Step {
+                    technique: self.technique(sashimi),
+                    placements: Vec::new(),
+                    eliminations,
+                    cells_involved: base.iter().flat_map(|&index| line(index).cells().filter(|&cell| has(cell))).collect(),
+                    fish: Some(FishSets {
+                        digit,
+                        base: base.iter().map(|&index| line(index)).collect(),
+                        cover: cover_lines,
+                        fins,
+                    }),
+                });
+            }
+        }
+        None
+    }
+}
+
+impl Propagator for Fish {
+    fn name(&self) -> &'static str {
+        match (self.size, self.finned) {
+            (2, false) => "x-wing",
+            (3, false) => "swordfish",
+            (_, false) => "jellyfish",
+            (2, true) => "finned-x-wing",
+            (3, true) => "finned-swordfish",
+            (_, true) => "finned-jellyfish",
+        }
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        for digit in 1..=9 {
+            for rows_first in [true, false] {
+                if let Some(step) = self.find(&candidates, digit, rows_first) {
+                    return Ok(Some(step));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+// Like "4 r15 c27", with the fins after an f: "4 r15 c27 fr1c9"
+impl fmt::Display for FishSets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.digit, lines(&self.base), lines(&self.cover))?;
+        for fin in &self.fins {
+            write!(f, " f{fin}")?;
+        }
+        Ok(())
+    }
+}
+
+// Like "r15" for rows 1 and 5
+fn lines(groups: &[Group]) -> String {
+    let mut text = String::new();
+    for group in groups {
+        match group {
+            Group::Row(index) if text.is_empty() => text.push_str(&format!("r{}", index + 1)),
+            Group::Column(index) if text.is_empty() => text.push_str(&format!("c{}", index + 1)),
+            Group::Row(index) | Group::Column(index) => text.push_str(&(index + 1).to_string()),
+            // fish are only ever made of rows and columns
+            Group::Square(_) => unreachable!(),
+        }
+    }
+    text
+}
@@ -0,0 +1,92 @@
+use crate::{
+    board::{
+        Board, Group,
+        cell::{CellValue, constrained_cell::ConstrainedCell},
+    },
+    error::Contradiction,
+    solvers::{
+        propagation::{CellQueue, Propagator, Step, candidate_grid},
+        stats::SolveStats,
+    },
+};
+
+// A value that only one cell of a row, column or square can take has to go there.
+// For example, if cell (0, 0) is the only cell in its row that can be a 2, it must be 2.
+pub struct HiddenSingle;
+
+impl Propagator for HiddenSingle {
+    fn name(&self) -> &'static str {
+        "hidden-single"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        for group in Group::all() {
+            let mut placed = 0u16;
+            for cell in group.cells() {
+                if let CellValue::Filled(value) = board.0[cell.row as usize][cell.column as usize].value {
+                    placed |= 1u16 << value;
+                }
+            }
+            for digit in (1..=9).filter(|digit| (placed >> digit) & 1u16 == 0) {
+                let mut places = group
+                    .cells()
+                    .filter(|cell| (candidates[cell.row as usize][cell.column as usize] >> digit) & 1u16 == 1);
+                match (places.next(), places.next()) {
+                    (None, _) => return Err(Contradiction::NoPlaceFor { digit, group }),
+                    (Some(position), None) => {
+                        return Ok(Some(Step {
+                            technique: "Hidden single",
+                            placements: vec![(position, digit)],
+                            eliminations: Vec::new(),
+                            cells_involved: group.cells().collect(),
+                            fish: None,
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    // Works through a queue of cells instead of finding one step at a time
+    fn propagate(&self, board: &mut Board<ConstrainedCell>, stats: &mut SolveStats) -> Result<bool, Contradiction> {
+        let mut cells = CellQueue::new(board);
+        let mut changed = false;
+
+        while let Some(position) = cells.pop() {
+            let candidates = cells.candidates(board, position);
+            if candidates == 0 {
+                return Err(Contradiction::NoCandidates(position));
+            }
+
+            for group in Group::containing(position) {
+                // Values the group already has, or that another of its empty cells can take
+                let mut elsewhere = 0u16;
+                for other in group.cells().filter(|&other| other != position) {
+                    elsewhere |= match board.0[other.row as usize][other.column as usize].value {
+                        CellValue::Filled(value) => 1u16 << value,
+                        CellValue::Empty => cells.candidates(board, other),
+                    };
+                }
+                // bits 1 to 9
+                let only_here = !elsewhere & 0b11_1111_1110;
+                if only_here == 0 {
+                    continue;
+                }
+                if only_here & !candidates != 0 {
+                    return Err(Contradiction::NoPlaceFor {
+                        digit: (only_here & !candidates).trailing_zeros() as i8,
+                        group,
+                    });
+                }
+                // If two values both need this cell, the re-check of its peers finds the second one has nowhere to go
+                cells.place(board, position, only_here.trailing_zeros() as i8, stats);
+                changed = true;
+                break;
+            }
+        }
+        Ok(changed)
+    }
+}
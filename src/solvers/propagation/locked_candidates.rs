@@ -0,0 +1,92 @@
+use crate::{
+    board::{
+        Board, Group,
+        cell::{CellPosition, constrained_cell::ConstrainedCell},
+    },
+    error::Contradiction,
+    solvers::propagation::{Propagator, Step, candidate_grid},
+};
+
+// If every cell of a square that can take a value is in the same row (or column), the square's copy of the value
+// has to go in that row, so no cell of the row outside the square can take it.
+// For example, if the 4 of the top left square can only go in r1c1 or r1c3, the rest of row 1 can't be 4.
+pub struct Pointing;
+
+// The other way round: if every cell of a row (or column) that can take a value is in the same square, the row's
+// copy of the value is in that square, so no cell of the square outside the row can take it.
+// Also known as box/line reduction.
+pub struct Claiming;
+
+impl Propagator for Pointing {
+    fn name(&self) -> &'static str {
+        "pointing"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        Ok(locked(
+            board,
+            "Pointing",
+            (0..9).map(Group::Square),
+            [|position: CellPosition| Group::Row(position.row), |position: CellPosition| Group::Column(position.column)],
+        ))
+    }
+}
+
+impl Propagator for Claiming {
+    fn name(&self) -> &'static str {
+        "claiming"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        Ok(locked(
+            board,
+            "Claiming",
+            (0..9).map(Group::Row).chain((0..9).map(Group::Column)),
+            [|position: CellPosition| Group::containing(position)[2]],
+        ))
+    }
+}
+
+// Finds a value whose open cells in one of the groups all share another group (the one a crossing gives for each
+// of them), and rules it out of the rest of that other group.
+// Values with a single open cell are left to the hidden single rule.
+fn locked<const N: usize>(
+    board: &Board<ConstrainedCell>,
+    technique: &'static str,
+    groups: impl Iterator<Item = Group>,
+    crossings: [fn(CellPosition) -> Group; N],
+) -> Option<Step> {
+    // The candidates left once the values in each row, column and square are taken out
+    let candidates = candidate_grid(board);
+    let has = |cell: &CellPosition, digit: i8| (candidates[cell.row as usize][cell.column as usize] >> digit) & 1u16 == 1;
+
+    for group in groups {
+        for digit in 1..=9 {
+            let spots: Vec<CellPosition> = group.cells().filter(|cell| has(cell, digit)).collect();
+            if spots.len() < 2 {
+                continue;
+            }
+            for crossing in crossings {
+                let line = crossing(spots[0]);
+                if spots.iter().any(|&spot| crossing(spot) != line) {
+                    continue;
+                }
+                let eliminations: Vec<(CellPosition, i8)> = line
+                    .cells()
+                    .filter(|cell| !spots.contains(cell) && has(cell, digit))
+                    .map(|cell| (cell, digit))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells_involved: spots,
+                        fish: None,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+
+use crate::{
+    board::{
+        Board,
+        cell::{
+            CellPosition, CellValue,
+            constrained_cell::{ConstrainedCell, ValueConstraint},
+        },
+    },
+    error::{Contradiction, SudokuError},
+    solvers::{
+        propagation::{
+            fish::{Fish, FishSets},
+            hidden_single::HiddenSingle,
+            locked_candidates::{Claiming, Pointing},
+            naked_single::NakedSingle,
+            subsets::{HiddenSubset, NakedSubset},
+            wings::{WWing, XyWing, XyzWing},
+        },
+        stats::SolveStats,
+    },
+};
+
+pub mod fish;
+pub mod hidden_single;
+pub mod locked_candidates;
+pub mod naked_single;
+pub mod subsets;
+pub mod wings;
+#[cfg(test)]
+mod tests;
+
+// One elimination rule, like naked singles.
+// Rules fill in cells (fixing them) and mark values as FixedNotAllowed in a cell's constraint map,
+// they never undo what another rule did, so running them in any order ends at the same board.
+// Send + Sync so solvers holding a pipeline can still be shared between threads.
+pub trait Propagator: Send + Sync {
+    // The name used by get_rule and --rules
+    fn name(&self) -> &'static str;
+
+    // The first place the rule applies, without changing the board. None once there is nothing left for it.
+    // A step always places or rules out something that is still open, so applying it changes the board.
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction>;
+
+    // Applies the rule everywhere it can, returns whether it changed anything
+    fn propagate(&self, board: &mut Board<ConstrainedCell>, stats: &mut SolveStats) -> Result<bool, Contradiction> {
+        let mut changed = false;
+        while let Some(step) = self.next_step(board)? {
+            stats.preprocessed_cells += step.placements.len() as u64;
+            step.apply(board);
+            changed = true;
+        }
+        Ok(changed)
+    }
+}
+
+// One use of a technique, in the terms people use when they solve by hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub technique: &'static str,
+    // (cell, digit) pairs the technique fills in
+    pub placements: Vec<(CellPosition, i8)>,
+    // (cell, digit) pairs the technique rules out
+    pub eliminations: Vec<(CellPosition, i8)>,
+    // The cells the reasoning looks at, for highlighting
+    pub cells_involved: Vec<CellPosition>,
+    // The lines a fish was found on, None for every other technique
+    pub fish: Option<FishSets>,
+}
+
+impl Step {
+    // Placing a digit also rules it out of the cell's empty peers
+    pub fn apply(&self, board: &mut Board<ConstrainedCell>) {
+        for &(position, digit) in &self.placements {
+            let cell = board.get_mut_cell(&position);
+            cell.fixed = true;
+            cell.value = CellValue::Filled(digit);
+            for peer in position.peers() {
+                rule_out(board, peer, digit);
+            }
+        }
+        for &(position, digit) in &self.eliminations {
+            rule_out(board, position, digit);
+        }
+    }
+}
+
+// Like "Naked single: r1c9=9", "Naked pair: r2c3<>5, r2c4<>5" or "X-Wing (4 r15 c27): r3c2<>4"
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.technique)?;
+        if let Some(fish) = &self.fish {
+            write!(f, " ({fish})")?;
+        }
+        write!(f, ":")?;
+        let placements = self.placements.iter().map(|(position, digit)| format!("{position}={digit}"));
+        let eliminations = self.eliminations.iter().map(|(position, digit)| format!("{position}<>{digit}"));
+        write!(f, " {}", placements.chain(eliminations).collect::<Vec<_>>().join(", "))
+    }
+}
+
+fn rule_out(board: &mut Board<ConstrainedCell>, position: CellPosition, digit: i8) {
+    let cell = board.get_mut_cell(&position);
+    if cell.value == CellValue::Empty && cell.value_constraint_map[digit as usize] == ValueConstraint::Allowed {
+        cell.value_constraint_map[digit as usize] = ValueConstraint::FixedNotAllowed;
+    }
+}
+
+// The candidates of every cell, 0 for filled ones: the values its constraint map allows and none of its peers have.
+// The grid next_step works on, so a rule sees the same candidates whichever rules ran before it.
+pub(crate) fn candidate_grid(board: &Board<ConstrainedCell>) -> [[u16; 9]; 9] {
+    let cells = CellQueue::new(board);
+    core::array::from_fn(|row| {
+        core::array::from_fn(|column| {
+            let position = CellPosition { row: row as i8, column: column as i8 };
+            match board.0[row][column].value {
+                CellValue::Empty => cells.candidates(board, position),
+                CellValue::Filled(_) => 0,
+            }
+        })
+    })
+}
+
+// Every way to pick size of count items, as bit masks of the picked indexes
+pub(crate) fn subsets(count: usize, size: u32) -> impl Iterator<Item = u16> {
+    (0u16..1 << count).filter(move |chosen| chosen.count_ones() == size)
+}
+
+pub(crate) fn picked<T: Copy>(items: &[T], chosen: u16) -> impl Iterator<Item = T> + '_ {
+    items
+        .iter()
+        .enumerate()
+        .filter(move |(index, _)| (chosen >> index) & 1u16 == 1)
+        .map(|(_, &item)| item)
+}
+
+// The digits set in a candidate mask, lowest first
+pub(crate) fn digits(mask: u16) -> impl Iterator<Item = i8> {
+    (1..=9).filter(move |digit| (mask >> digit) & 1u16 == 1)
+}
+
+// Every name accepted by get_rule, cheapest first
+pub const RULE_NAMES: [&str; 19] = [
+    "naked-single",
+    "hidden-single",
+    "pointing",
+    "claiming",
+    "naked-pair",
+    "hidden-pair",
+    "naked-triple",
+    "hidden-triple",
+    "naked-quad",
+    "hidden-quad",
+    "x-wing",
+    "swordfish",
+    "jellyfish",
+    "finned-x-wing",
+    "finned-swordfish",
+    "finned-jellyfish",
+    "xy-wing",
+    "xyz-wing",
+    "w-wing",
+];
+
+pub fn get_rule(name: &str) -> Result<Box<dyn Propagator>, SudokuError> {
+    match name.to_lowercase().as_str() {
+        "naked-single" => Ok(Box::new(NakedSingle)),
+        "hidden-single" => Ok(Box::new(HiddenSingle)),
+        "pointing" => Ok(Box::new(Pointing)),
+        "claiming" => Ok(Box::new(Claiming)),
+        "naked-pair" => Ok(Box::new(NakedSubset::PAIR)),
+        "hidden-pair" => Ok(Box::new(HiddenSubset::PAIR)),
+        "naked-triple" => Ok(Box::new(NakedSubset::TRIPLE)),
+        "hidden-triple" => Ok(Box::new(HiddenSubset::TRIPLE)),
+        "naked-quad" => Ok(Box::new(NakedSubset::QUAD)),
+        "hidden-quad" => Ok(Box::new(HiddenSubset::QUAD)),
+        "x-wing" => Ok(Box::new(Fish::X_WING)),
+        "swordfish" => Ok(Box::new(Fish::SWORDFISH)),
+        "jellyfish" => Ok(Box::new(Fish::JELLYFISH)),
+        "finned-x-wing" => Ok(Box::new(Fish::FINNED_X_WING)),
+        "finned-swordfish" => Ok(Box::new(Fish::FINNED_SWORDFISH)),
+        "finned-jellyfish" => Ok(Box::new(Fish::FINNED_JELLYFISH)),
+        "xy-wing" => Ok(Box::new(XyWing)),
+        "xyz-wing" => Ok(Box::new(XyzWing)),
+        "w-wing" => Ok(Box::new(WWing)),
+        _ => Err(SudokuError::UnknownRule(name.to_owned())),
+    }
+}
+
+// Runs its rules in order until none of them change the board any more.
+// After any change it starts again from the first rule, so the cheap ones should go first.
+pub struct Pipeline {
+    rules: Vec<Box<dyn Propagator>>,
+}
+
+impl Pipeline {
+    pub fn new(rules: Vec<Box<dyn Propagator>>) -> Self {
+        Pipeline { rules }
+    }
+
+    // Takes the same names as get_rule
+    pub fn from_names(names: &[&str]) -> Result<Self, SudokuError> {
+        Ok(Pipeline::new(
+            names.iter().map(|name| get_rule(name)).collect::<Result<_, _>>()?,
+        ))
+    }
+
+    // Every filled cell counts, so a player's entries should be fixed before this runs (see Board::with_entries_fixed).
+    // Stops at the first contradiction, whatever the rules did up to that point still holds.
+    pub fn run(&self, board: &mut Board<ConstrainedCell>, stats: &mut SolveStats) -> Result<(), Contradiction> {
+        if let Some(clash) = board.find_clash() {
+            return Err(clash);
+        }
+        let mut index = 0;
+        while let Some(rule) = self.rules.get(index) {
+            index = if rule.propagate(board, stats)? { 0 } else { index + 1 };
+        }
+        Ok(())
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Pipeline::new(vec![Box::new(NakedSingle), Box::new(HiddenSingle)])
+    }
+}
+
+// The values placed in each row, column and square, and the empty cells a rule still has to look at.
+// Placing a value queues the cell's empty peers again, since the value might have made something of them.
+pub(crate) struct CellQueue {
+    row_forbidden: [u16; 9],
+    col_forbidden: [u16; 9],
+    square_forbidden: [u16; 9],
+    queue: Vec<CellPosition>,
+    // so we don't push the same pos in twice
+    in_queue: [[bool; 9]; 9],
+}
+
+impl CellQueue {
+    // Starts with every empty cell queued
+    pub(crate) fn new(board: &Board<ConstrainedCell>) -> Self {
+        let mut cells = CellQueue {
+            row_forbidden: [0; 9],
+            col_forbidden: [0; 9],
+            square_forbidden: [0; 9],
+            queue: Vec::with_capacity(81),
+            in_queue: [[false; 9]; 9],
+        };
+        ConstrainedCell::calculate_forbidden_matrices(
+            board,
+            &mut cells.row_forbidden,
+            &mut cells.col_forbidden,
+            &mut cells.square_forbidden,
+            &mut cells.queue,
+            &mut cells.in_queue,
+            // unused
+            &mut [[false; 9]; 9],
+        );
+        cells
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<CellPosition> {
+        let position = self.queue.pop()?;
+        self.in_queue[position.row as usize][position.column as usize] = false;
+        Some(position)
+    }
+
+    // Values one of the cell's peers already has
+    pub(crate) fn forbidden(&self, position: CellPosition) -> u16 {
+        self.row_forbidden[position.row as usize]
+            | self.col_forbidden[position.column as usize]
+            | self.square_forbidden[square_index(position)]
+    }
+
+    // Values the cell's own constraints allow and none of its peers have
+    pub(crate) fn candidates(&self, board: &Board<ConstrainedCell>, position: CellPosition) -> u16 {
+        board.0[position.row as usize][position.column as usize].candidates() & !self.forbidden(position)
+    }
+
+    // Fixes the value in the cell and queues every non fixed peer to be checked again
+    pub(crate) fn place(
+        &mut self,
+        board: &mut Board<ConstrainedCell>,
+        position: CellPosition,
+        value: i8,
+        stats: &mut SolveStats,
+    ) {
+        let cell = board.get_mut_cell(&position);
+        cell.fixed = true;
+        cell.value = CellValue::Filled(value);
+        stats.preprocessed_cells += 1;
+
+        self.row_forbidden[position.row as usize] |= 1u16 << value;
+        self.col_forbidden[position.column as usize] |= 1u16 << value;
+        self.square_forbidden[square_index(position)] |= 1u16 << value;
+
+        for peer in position.peers() {
+            if !board.0[peer.row as usize][peer.column as usize].fixed
+                && !self.in_queue[peer.row as usize][peer.column as usize]
+            {
+                stats.queue_pushes += 1;
+                self.queue.push(peer);
+                self.in_queue[peer.row as usize][peer.column as usize] = true;
+            }
+        }
+    }
+}
+
+fn square_index(position: CellPosition) -> usize {
+    (((position.row / 3) * 3) + (position.column / 3)) as usize
+}
@@ -0,0 +1,79 @@
+use crate::{
+    board::{
+        Board,
+        cell::{
+            CellPosition, CellValue,
+            constrained_cell::{ConstrainedCell, ValueConstraint},
+        },
+    },
+    error::Contradiction,
+    solvers::{
+        propagation::{CellQueue, Propagator, Step, candidate_grid},
+        stats::SolveStats,
+    },
+};
+
+// A cell that only one value fits in has to take it.
+// Along the way every value one of its peers already has is marked as not allowed in the cell.
+pub struct NakedSingle;
+
+impl Propagator for NakedSingle {
+    fn name(&self) -> &'static str {
+        "naked-single"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        for (index, cell) in board.0.iter().flatten().enumerate() {
+            let position = CellPosition {
+                row: (index / 9) as i8,
+                column: (index % 9) as i8,
+            };
+            let cell_candidates = candidates[index / 9][index % 9];
+            if cell.value != CellValue::Empty || cell_candidates.count_ones() > 1 {
+                continue;
+            }
+            if cell_candidates == 0 {
+                return Err(Contradiction::NoCandidates(position));
+            }
+            return Ok(Some(Step {
+                technique: "Naked single",
+                placements: vec![(position, cell_candidates.trailing_zeros() as i8)],
+                eliminations: Vec::new(),
+                cells_involved: vec![position],
+                fish: None,
+            }));
+        }
+        Ok(None)
+    }
+
+    // Works through a queue of cells instead of finding one step at a time
+    fn propagate(&self, board: &mut Board<ConstrainedCell>, stats: &mut SolveStats) -> Result<bool, Contradiction> {
+        let mut cells = CellQueue::new(board);
+        let mut changed = false;
+
+        while let Some(position) = cells.pop() {
+            let forbidden = cells.forbidden(position);
+            let cell = board.get_mut_cell(&position);
+            for value in 1..=9 {
+                if (forbidden >> value) & 1u16 == 1
+                    && cell.value_constraint_map[value] == ValueConstraint::Allowed
+                {
+                    cell.value_constraint_map[value] = ValueConstraint::FixedNotAllowed;
+                    changed = true;
+                }
+            }
+
+            let candidates = cell.candidates();
+            match candidates.count_ones() {
+                0 => return Err(Contradiction::NoCandidates(position)),
+                1 => {
+                    cells.place(board, position, candidates.trailing_zeros() as i8, stats);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        Ok(changed)
+    }
+}
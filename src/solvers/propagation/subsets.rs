@@ -0,0 +1,158 @@
+use crate::{
+    board::{
+        Board, Group,
+        cell::{CellPosition, CellValue, constrained_cell::ConstrainedCell},
+    },
+    error::Contradiction,
+    solvers::propagation::{Propagator, Step, candidate_grid, digits, picked, subsets},
+};
+
+// If n cells of a row, column or square can only take the same n values between them, those values go in those
+// cells, so no other cell of the group can take them.
+// For example, two cells of a row that can both only be 3 or 7 rule out 3 and 7 everywhere else in the row.
+pub struct NakedSubset {
+    size: u32,
+}
+
+// If n values can only go in the same n cells of a row, column or square, those cells have to take them,
+// so they can't take anything else.
+// For example, if 3 and 7 only fit in two cells of a row, every other value is ruled out of those two cells.
+pub struct HiddenSubset {
+    size: u32,
+}
+
+impl NakedSubset {
+    pub const PAIR: NakedSubset = NakedSubset { size: 2 };
+    pub const TRIPLE: NakedSubset = NakedSubset { size: 3 };
+    pub const QUAD: NakedSubset = NakedSubset { size: 4 };
+
+    fn technique(&self) -> &'static str {
+        match self.size {
+            2 => "Naked pair",
+            3 => "Naked triple",
+            _ => "Naked quad",
+        }
+    }
+}
+
+impl HiddenSubset {
+    pub const PAIR: HiddenSubset = HiddenSubset { size: 2 };
+    pub const TRIPLE: HiddenSubset = HiddenSubset { size: 3 };
+    pub const QUAD: HiddenSubset = HiddenSubset { size: 4 };
+
+    fn technique(&self) -> &'static str {
+        match self.size {
+            2 => "Hidden pair",
+            3 => "Hidden triple",
+            _ => "Hidden quad",
+        }
+    }
+}
+
+impl Propagator for NakedSubset {
+    fn name(&self) -> &'static str {
+        match self.size {
+            2 => "naked-pair",
+            3 => "naked-triple",
+            _ => "naked-quad",
+        }
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        let candidates_of = |cell: &CellPosition| candidates[cell.row as usize][cell.column as usize];
+        for group in Group::all() {
+            let empty: Vec<CellPosition> = group.cells().filter(|cell| candidates_of(cell) != 0).collect();
+            // Cells with a single value are naked singles, not part of a subset
+            let open: Vec<CellPosition> = empty
+                .iter()
+                .copied()
+                .filter(|cell| (2..=self.size).contains(&candidates_of(cell).count_ones()))
+                .collect();
+
+            for chosen in subsets(open.len(), self.size) {
+                let cells: Vec<CellPosition> = picked(&open, chosen).collect();
+                let values = cells.iter().fold(0u16, |values, cell| values | candidates_of(cell));
+                if values.count_ones() != self.size {
+                    continue;
+                }
+                let eliminations: Vec<(CellPosition, i8)> = empty
+                    .iter()
+                    .filter(|cell| !cells.contains(cell))
+                    .flat_map(|&cell| digits(candidates_of(&cell) & values).map(move |digit| (cell, digit)))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Ok(Some(Step {
+                        technique: self.technique(),
+                        placements: Vec::new(),
+                        eliminations,
+                        cells_involved: cells,
+                        fish: None,
+                    }));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Propagator for HiddenSubset {
+    fn name(&self) -> &'static str {
+        match self.size {
+            2 => "hidden-pair",
+            3 => "hidden-triple",
+            _ => "hidden-quad",
+        }
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        for group in Group::all() {
+            let cells: Vec<CellPosition> = group.cells().collect();
+            // For each digit, a bit for every cell of the group it can go in
+            let mut places = [0u16; 10];
+            let mut placed = 0u16;
+            for (index, cell) in cells.iter().enumerate() {
+                if let CellValue::Filled(value) = board.0[cell.row as usize][cell.column as usize].value {
+                    placed |= 1u16 << value;
+                }
+                for digit in digits(candidates[cell.row as usize][cell.column as usize]) {
+                    places[digit as usize] |= 1u16 << index;
+                }
+            }
+            // Digits with one place are hidden singles, and ones with none are the hidden single rule's contradiction
+            let open: Vec<i8> = (1..=9)
+                .filter(|&digit| {
+                    (placed >> digit) & 1u16 == 0 && (2..=self.size).contains(&places[digit as usize].count_ones())
+                })
+                .collect();
+
+            for chosen in subsets(open.len(), self.size) {
+                let values: Vec<i8> = picked(&open, chosen).collect();
+                let spots = values.iter().fold(0u16, |spots, &digit| spots | places[digit as usize]);
+                if spots.count_ones() != self.size {
+                    continue;
+                }
+                let values_mask = values.iter().fold(0u16, |mask, &digit| mask | 1u16 << digit);
+                let subset_cells: Vec<CellPosition> = picked(&cells, spots).collect();
+                let eliminations: Vec<(CellPosition, i8)> = subset_cells
+                    .iter()
+                    .flat_map(|&cell| {
+                        digits(candidates[cell.row as usize][cell.column as usize] & !values_mask)
+                            .map(move |digit| (cell, digit))
+                    })
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Ok(Some(Step {
+                        technique: self.technique(),
+                        placements: Vec::new(),
+                        eliminations,
+                        cells_involved: subset_cells,
+                        fish: None,
+                    }));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
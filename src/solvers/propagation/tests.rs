@@ -0,0 +1,324 @@
+use crate::{
+    board::{
+        Board, Group,
+        cell::{
+            CellPosition, CellValue, IsCell,
+            constrained_cell::{ConstrainedCell, ValueConstraint},
+        },
+    },
+    error::{Contradiction, SudokuError},
+    solvers::{
+        propagation::{
+            Pipeline, Propagator, RULE_NAMES, Step, get_rule,
+            fish::Fish,
+            hidden_single::HiddenSingle,
+            locked_candidates::{Claiming, Pointing},
+            naked_single::NakedSingle,
+            subsets::{HiddenSubset, NakedSubset},
+            wings::{WWing, XyWing, XyzWing},
+        },
+        stats::SolveStats,
+    },
+};
+
+const EASY: &str = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+const EASY_SOLUTION: &str = "891457632234681597675392841183975264762148953459263718947816325318529476526734189";
+
+fn propagated(puzzle: &str, pipeline: &Pipeline) -> (Board<ConstrainedCell>, SolveStats) {
+    let mut board = Board::<ConstrainedCell>::parse_puzzle_string(puzzle).expect("valid puzzle");
+    let mut stats = SolveStats::default();
+    pipeline.run(&mut board, &mut stats).expect("no contradiction");
+    (board, stats)
+}
+
+// Every filled cell agrees with the solution
+fn agrees_with(board: &Board<ConstrainedCell>, solution: &str) -> bool {
+    board
+        .to_string()
+        .chars()
+        .zip(solution.chars())
+        .all(|(cell, expected)| cell == '.' || cell == expected)
+}
+
+#[test]
+fn runs_the_rules_to_a_fixpoint() {
+    let (mut board, stats) = propagated(EASY, &Pipeline::default());
+    assert!(stats.preprocessed_cells > 0);
+    assert!(agrees_with(&board, EASY_SOLUTION), "{board}");
+
+    let mut stats = SolveStats::default();
+    for rule in [&NakedSingle as &dyn Propagator, &HiddenSingle] {
+        assert_eq!(rule.propagate(&mut board, &mut stats), Ok(false), "{}", rule.name());
+    }
+    assert_eq!(stats.preprocessed_cells, 0);
+}
+
+#[test]
+fn rule_order_does_not_change_the_result() {
+    let (forwards, _) = propagated(EASY, &Pipeline::from_names(&["naked-single", "hidden-single"]).expect("known rules"));
+    let (backwards, _) = propagated(EASY, &Pipeline::from_names(&["hidden-single", "naked-single"]).expect("known rules"));
+    assert_eq!(forwards.to_string(), backwards.to_string());
+}
+
+// An empty board with the givens at (row, column, digit)
+fn puzzle(givens: &[(usize, usize, u8)]) -> String {
+    let mut puzzle = vec![b'.'; 81];
+    for &(row, column, digit) in givens {
+        puzzle[row * 9 + column] = b'0' + digit;
+    }
+    String::from_utf8(puzzle).expect("ascii")
+}
+
+#[test]
+fn hidden_single_finds_the_only_cell_for_a_value() {
+    // The 1s in rows 2 and 3 leave r1c1 as the only place for a 1 in the top left square
+    let puzzle = puzzle(&[(0, 1, 2), (0, 2, 3), (1, 4, 1), (2, 7, 1)]);
+    let (board, stats) = propagated(&puzzle, &Pipeline::new(vec![Box::new(HiddenSingle)]));
+    assert_eq!(board.to_string().chars().next(), Some('1'));
+    assert!(stats.preprocessed_cells > 0);
+}
+
+#[test]
+fn reports_contradictions() {
+    // r1c9 has no value left
+    let mut board = Board::<ConstrainedCell>::parse_puzzle_string(
+        "12345678.........9...............................................................",
+    )
+    .expect("valid puzzle");
+    assert_eq!(
+        Pipeline::default().run(&mut board, &mut SolveStats::default()),
+        Err(Contradiction::NoCandidates(CellPosition { row: 0, column: 8 }))
+    );
+
+    // Row 1 still needs an 8 and a 9, but the 9 in r2c8 rules 9 out of both its empty cells
+    let givens: Vec<_> = (0..7).map(|column| (0, column, column as u8 + 1)).chain([(1, 7, 9)]).collect();
+    let mut board = Board::<ConstrainedCell>::parse_puzzle_string(&puzzle(&givens)).expect("valid puzzle");
+    assert_eq!(
+        Pipeline::new(vec![Box::new(HiddenSingle)]).run(&mut board, &mut SolveStats::default()),
+        Err(Contradiction::NoPlaceFor { digit: 9, group: Group::Row(0) })
+    );
+}
+
+#[test]
+fn looks_up_rules_by_name() {
+    for name in RULE_NAMES {
+        assert_eq!(get_rule(name).map(|rule| rule.name()).ok(), Some(name));
+    }
+    assert_eq!(
+        Pipeline::from_names(&["naked-single", "x-wing-ish"]).err(),
+        Some(SudokuError::UnknownRule("x-wing-ish".to_string()))
+    );
+}
+
+// Only has next_step, so propagate is the default one built on it
+struct StepByStep(Box<dyn Propagator>);
+
+impl Propagator for StepByStep {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        self.0.next_step(board)
+    }
+}
+
+#[test]
+fn next_step_finds_what_propagate_does() {
+    for rule in RULE_NAMES {
+        let (queued, queued_stats) = propagated(EASY, &Pipeline::from_names(&[rule]).expect("known rule"));
+        let (stepped, stepped_stats) = propagated(
+            EASY,
+            &Pipeline::new(vec![Box::new(StepByStep(get_rule(rule).expect("known rule")))]),
+        );
+        assert_eq!(queued.to_string(), stepped.to_string(), "{rule}");
+        assert_eq!(queued_stats.preprocessed_cells, stepped_stats.preprocessed_cells, "{rule}");
+    }
+}
+
+#[test]
+fn steps_say_what_they_did() {
+    let puzzle = puzzle(&[(0, 1, 2), (0, 2, 3), (1, 4, 1), (2, 7, 1)]);
+    let mut board = Board::<ConstrainedCell>::parse_puzzle_string(&puzzle).expect("valid puzzle");
+    let step = HiddenSingle.next_step(&board).expect("no contradiction").expect("a hidden single");
+    assert_eq!(step.to_string(), "Hidden single: r1c1=1");
+    assert_eq!(step.cells_involved.len(), 9);
+
+    // Applying it also rules the 1 out of r1c1's peers
+    step.apply(&mut board);
+    assert_eq!(board.0[0][0].value, CellValue::Filled(1));
+    assert!(!board.0[0][5].is_allowed(1) && !board.0[5][0].is_allowed(1) && !board.0[2][2].is_allowed(1));
+    assert!(board.0[5][5].is_allowed(1));
+
+    let step = Step {
+        technique: "Naked pair",
+        placements: Vec::new(),
+        eliminations: vec![(CellPosition { row: 1, column: 2 }, 5), (CellPosition { row: 1, column: 3 }, 5)],
+        cells_involved: Vec::new(),
+        fish: None,
+    };
+    assert_eq!(step.to_string(), "Naked pair: r2c3<>5, r2c4<>5");
+}
+
+// Singles can't fill in a single cell of this one, the pair of 1s and 6s in row 1 is what opens it up
+const PAIR: &str = "4.....938.32.941...953..24.37.6.9..4529..16736.47.3.9.957..83....39..4..24..3.7.9";
+const PAIR_SOLUTION: &str = "461572938732894156895316247378629514529481673614753892957248361183967425246135789";
+
+fn at(row: i8, column: i8) -> CellPosition {
+    CellPosition { row, column }
+}
+
+#[test]
+fn subsets_open_up_what_singles_cannot() {
+    let (_, stats) = propagated(PAIR, &Pipeline::default());
+    assert_eq!(stats.preprocessed_cells, 0);
+
+    let board = Board::<ConstrainedCell>::parse_puzzle_string(PAIR).expect("valid puzzle");
+    let step = NakedSubset::PAIR.next_step(&board).expect("no contradiction").expect("a naked pair");
+    assert_eq!(step.to_string(), "Naked pair: r1c4<>1, r1c5<>1, r1c5<>6, r1c6<>6");
+    assert_eq!(step.cells_involved, [at(0, 1), at(0, 2)]);
+    // The same pair seen from the values' side, 1 and 6 only fit in r3c1 and r3c5
+    let step = HiddenSubset::PAIR.next_step(&board).expect("no contradiction").expect("a hidden pair");
+    assert_eq!(step.to_string(), "Hidden pair: r3c1<>7, r3c5<>6, r3c5<>7");
+
+    for rule in ["naked-pair", "hidden-pair", "hidden-triple"] {
+        let (board, _) = propagated(PAIR, &Pipeline::from_names(&["naked-single", "hidden-single", rule]).expect("known rules"));
+        assert_eq!(board.to_string(), PAIR_SOLUTION, "{rule}");
+    }
+}
+
+// An empty board where each of the given cells can only take the given values
+fn with_candidates(cells: &[(i8, i8, &[usize])]) -> Board<ConstrainedCell> {
+    let mut board = Board::<ConstrainedCell>::parse_puzzle_string(&".".repeat(81)).expect("valid puzzle");
+    for &(row, column, values) in cells {
+        for value in (1..=9).filter(|value| !values.contains(value)) {
+            board.get_mut_cell(&at(row, column)).value_constraint_map[value] = ValueConstraint::FixedNotAllowed;
+        }
+    }
+    board
+}
+
+#[test]
+fn naked_triple_needs_no_cell_with_all_three_values() {
+    // r1c1, r1c2 and r1c3 can only be 1 or 2, 2 or 3, and 1 or 3
+    let mut board = with_candidates(&[(0, 0, &[1, 2]), (0, 1, &[2, 3]), (0, 2, &[1, 3])]);
+    assert_eq!(NakedSubset::PAIR.next_step(&board), Ok(None));
+
+    let step = NakedSubset::TRIPLE.next_step(&board).expect("no contradiction").expect("a naked triple");
+    assert_eq!(step.technique, "Naked triple");
+    assert_eq!(step.cells_involved, [at(0, 0), at(0, 1), at(0, 2)]);
+    let expected: Vec<_> = (3..9).flat_map(|column| (1..=3).map(move |digit| (at(0, column), digit))).collect();
+    assert_eq!(step.eliminations, expected);
+
+    // Once it is applied, the square is all that is left for the triple
+    step.apply(&mut board);
+    let step = NakedSubset::TRIPLE.next_step(&board).expect("no contradiction").expect("a naked triple");
+    assert!(step.eliminations.iter().all(|(position, _)| position.row > 0 && position.column < 3));
+}
+
+#[test]
+fn locked_candidates_rule_out_the_rest_of_the_line() {
+    // The bottom two rows of the top left square are full, so its 1 is in row 1
+    let givens: Vec<_> = (0..6).map(|index| (1 + index / 3, index % 3, index as u8 + 2)).collect();
+    let board = Board::<ConstrainedCell>::parse_puzzle_string(&puzzle(&givens)).expect("valid puzzle");
+    let step = Pointing.next_step(&board).expect("no contradiction").expect("a pointing step");
+    assert_eq!(step.to_string(), "Pointing: r1c4<>1, r1c5<>1, r1c6<>1, r1c7<>1, r1c8<>1, r1c9<>1");
+    assert_eq!(step.cells_involved, [at(0, 0), at(0, 1), at(0, 2)]);
+
+    // Row 1 is full outside the top left square, so its 1 is in that square
+    let givens: Vec<_> = (0..6).map(|index| (0, index + 3, index as u8 + 2)).collect();
+    let board = Board::<ConstrainedCell>::parse_puzzle_string(&puzzle(&givens)).expect("valid puzzle");
+    let step = Claiming.next_step(&board).expect("no contradiction").expect("a claiming step");
+    assert_eq!(step.to_string(), "Claiming: r2c1<>1, r2c2<>1, r2c3<>1, r3c1<>1, r3c2<>1, r3c3<>1");
+    assert_eq!(Pointing.next_step(&board), Ok(None));
+
+    // Singles get stuck on this one, locked candidates finish it
+    let (board, _) = propagated(EASY, &Pipeline::from_names(&["naked-single", "hidden-single", "pointing", "claiming"]).expect("known rules"));
+    assert_eq!(board.to_string(), EASY_SOLUTION);
+}
+
+// An empty board where the digit only fits in the given columns of each of the given rows
+fn only_in_columns(digit: usize, rows: &[(i8, &[i8])]) -> Board<ConstrainedCell> {
+    let others: Vec<usize> = (1..=9).filter(|&value| value != digit).collect();
+    let others = others.as_slice();
+    let cells: Vec<(i8, i8, &[usize])> = rows
+        .iter()
+        .flat_map(|&(row, columns)| {
+            (0..9).filter(move |column| !columns.contains(column)).map(move |column| (row, column, others))
+        })
+        .collect();
+    with_candidates(&cells)
+}
+
+#[test]
+fn fish_rule_out_the_rest_of_the_cover_set() {
+    let board = only_in_columns(1, &[(0, &[1, 6]), (4, &[1, 6])]);
+    let step = Fish::X_WING.next_step(&board).expect("no contradiction").expect("an x-wing");
+    assert!(step.to_string().starts_with("X-Wing (1 r15 c27): r2c2<>1, r3c2<>1, r4c2<>1, r6c2<>1"), "{step}");
+    assert_eq!(step.eliminations.len(), 14);
+    let fish = step.fish.expect("fish sets");
+    assert_eq!(fish.base, [Group::Row(0), Group::Row(4)]);
+    assert_eq!(fish.cover, [Group::Column(1), Group::Column(6)]);
+    assert!(fish.fins.is_empty());
+
+    let board = only_in_columns(1, &[(0, &[1, 4]), (4, &[4, 7]), (8, &[1, 7])]);
+    assert_eq!(Fish::X_WING.next_step(&board), Ok(None));
+    let step = Fish::SWORDFISH.next_step(&board).expect("no contradiction").expect("a swordfish");
+    assert!(step.to_string().starts_with("Swordfish (1 r159 c258): "), "{step}");
+    assert_eq!(step.eliminations.len(), 18);
+    assert_eq!(step.cells_involved.len(), 6);
+    // Turned on its side it is found with columns as the base set
+    let mut transposed = board.clone();
+    for (row, column) in (0..9).flat_map(|row| (0..9).map(move |column| (row, column))) {
+        transposed.0[row][column].value_constraint_map = board.0[column][row].value_constraint_map;
+    }
+    let step = Fish::SWORDFISH.next_step(&transposed).expect("no contradiction").expect("a swordfish");
+    assert!(step.to_string().starts_with("Swordfish (1 c159 r258): "), "{step}");
+}
+
+#[test]
+fn finned_fish_only_rule_out_what_the_fins_see() {
+    // r1c8 is a fin, the x-wing on r1 and r5 only holds if it is not a 1
+    let board = only_in_columns(1, &[(0, &[1, 6, 7]), (4, &[1, 6])]);
+    assert_eq!(Fish::X_WING.next_step(&board), Ok(None));
+    let step = Fish::FINNED_X_WING.next_step(&board).expect("no contradiction").expect("a finned x-wing");
+    assert_eq!(step.to_string(), "Finned X-Wing (1 r15 c27 fr1c8): r2c7<>1, r3c7<>1");
+
+    // Without the fins, r1 only has a single 1 left in the cover set
+    let board = only_in_columns(1, &[(0, &[1, 7, 8]), (4, &[1, 6])]);
+    let step = Fish::FINNED_X_WING.next_step(&board).expect("no contradiction").expect("a sashimi x-wing");
+    assert_eq!(step.to_string(), "Sashimi X-Wing (1 r15 c27 fr1c8 fr1c9): r2c7<>1, r3c7<>1");
+    assert_eq!(step.fish.map(|fish| fish.fins.len()), Some(2));
+
+    // Fins in two squares rule nothing out
+    let board = only_in_columns(1, &[(0, &[1, 6, 7]), (4, &[1, 4, 6])]);
+    assert_eq!(Fish::FINNED_X_WING.next_step(&board), Ok(None));
+}
+
+#[test]
+fn xy_wing_rules_out_what_both_pincers_see() {
+    // Pivot r1c1 is 1 or 2, so either r1c5 or r3c2 is a 3
+    let board = with_candidates(&[(0, 0, &[1, 2]), (0, 4, &[1, 3]), (2, 1, &[2, 3])]);
+    let step = XyWing.next_step(&board).expect("no contradiction").expect("an xy-wing");
+    assert_eq!(step.to_string(), "XY-Wing: r1c2<>3, r1c3<>3, r3c4<>3, r3c5<>3, r3c6<>3");
+    assert_eq!(step.cells_involved, [at(0, 0), at(0, 4), at(2, 1)]);
+    assert_eq!(XyzWing.next_step(&board), Ok(None));
+
+    // With a 3 in the pivot as well, the cells also have to see the pivot
+    let board = with_candidates(&[(0, 0, &[1, 2, 3]), (0, 4, &[1, 3]), (2, 1, &[2, 3])]);
+    let step = XyzWing.next_step(&board).expect("no contradiction").expect("an xyz-wing");
+    assert_eq!(step.to_string(), "XYZ-Wing: r1c2<>3, r1c3<>3");
+    assert_eq!(XyWing.next_step(&board), Ok(None));
+}
+
+#[test]
+fn w_wing_needs_a_strong_link() {
+    // r1c1 and r9c9 are both 1 or 2, and row 5 only has room for a 1 in r5c1 and r5c9
+    let mut cells: Vec<(i8, i8, &[usize])> = vec![(0, 0, &[1, 2]), (8, 8, &[1, 2])];
+    assert_eq!(WWing.next_step(&with_candidates(&cells)), Ok(None));
+
+    cells.extend((1..8).map(|column| (4, column, &[2, 3, 4, 5, 6, 7, 8, 9][..])));
+    let step = WWing.next_step(&with_candidates(&cells)).expect("no contradiction").expect("a w-wing");
+    assert_eq!(step.to_string(), "W-Wing: r1c9<>2, r9c1<>2");
+    assert_eq!(step.cells_involved, [at(0, 0), at(8, 8), at(4, 0), at(4, 8)]);
+}
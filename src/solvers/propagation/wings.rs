@@ -0,0 +1,147 @@
+use crate::{
+    board::{
+        Board, Group,
+        cell::{CellPosition, constrained_cell::ConstrainedCell},
+    },
+    error::Contradiction,
+    solvers::propagation::{Propagator, Step, candidate_grid, digits},
+};
+
+// A pivot cell that can only be x or y, with two peers (the pincers) that can only be x or z and y or z.
+// Whichever value the pivot takes, one of the pincers has to be z, so cells that see both pincers can't be z.
+pub struct XyWing;
+
+// The same with a pivot that can be x, y or z. If the pivot is z itself the pincers don't have to be,
+// so only the cells that see the pivot as well as both pincers lose z.
+pub struct XyzWing;
+
+// Two cells that can only be x or y and don't see each other, joined by a strong link on x: a row, column or
+// square where x only fits in two cells, one seeing each of them. One of those two cells is x, so one of the pair
+// can't be x and has to be y, and cells that see both of the pair can't be y.
+pub struct WWing;
+
+impl Propagator for XyWing {
+    fn name(&self) -> &'static str {
+        "xy-wing"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        Ok(pivot_wing(board, 2, "XY-Wing"))
+    }
+}
+
+impl Propagator for XyzWing {
+    fn name(&self) -> &'static str {
+        "xyz-wing"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        Ok(pivot_wing(board, 3, "XYZ-Wing"))
+    }
+}
+
+impl Propagator for WWing {
+    fn name(&self) -> &'static str {
+        "w-wing"
+    }
+
+    fn next_step(&self, board: &Board<ConstrainedCell>) -> Result<Option<Step>, Contradiction> {
+        let candidates = candidate_grid(board);
+        let pairs: Vec<CellPosition> = positions().filter(|&cell| mask(&candidates, cell).count_ones() == 2).collect();
+
+        for (index, &first) in pairs.iter().enumerate() {
+            for &second in &pairs[index + 1..] {
+                let values = mask(&candidates, first);
+                if mask(&candidates, second) != values || first.sees(second) {
+                    continue;
+                }
+                for linked in digits(values) {
+                    let other = digits(values & !(1u16 << linked)).next().expect("two values");
+                    for group in Group::all() {
+                        let spots: Vec<CellPosition> =
+                            group.cells().filter(|&cell| (mask(&candidates, cell) >> linked) & 1u16 == 1).collect();
+                        let &[one, two] = spots.as_slice() else {
+                            continue;
+                        };
+                        if spots.contains(&first) || spots.contains(&second) {
+                            continue;
+                        }
+                        let joined = (one.sees(first) && two.sees(second)) || (one.sees(second) && two.sees(first));
+                        if !joined {
+                            continue;
+                        }
+                        let eliminations = seen_by_all(&candidates, &[first, second], other);
+                        if !eliminations.is_empty() {
+                            return Ok(Some(Step {
+                                technique: "W-Wing",
+                                placements: Vec::new(),
+                                eliminations,
+                                cells_involved: vec![first, second, one, two],
+                                fish: None,
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+// XY-Wings have a pivot with 2 values, XYZ-Wings one with 3.
+// Each pincer has two values, one shared with the other pincer (z) and the rest from the pivot.
+fn pivot_wing(board: &Board<ConstrainedCell>, pivot_size: u32, technique: &'static str) -> Option<Step> {
+    let candidates = candidate_grid(board);
+    for pivot in positions().filter(|&cell| mask(&candidates, cell).count_ones() == pivot_size) {
+        let values = mask(&candidates, pivot);
+        // Peers with two values, at least one of them the pivot's
+        let pincers: Vec<CellPosition> = pivot
+            .peers()
+            .filter(|&cell| {
+                let pincer = mask(&candidates, cell);
+                pincer.count_ones() == 2 && pincer & values != 0 && (pincer & !values).count_ones() + pivot_size == 3
+            })
+            .collect();
+
+        for (index, &first) in pincers.iter().enumerate() {
+            for &second in &pincers[index + 1..] {
+                let (first_values, second_values) = (mask(&candidates, first), mask(&candidates, second));
+                let shared = first_values & second_values;
+                // Between them the pincers cover the pivot's values, and share one more
+                if shared.count_ones() != 1 || (first_values | second_values) & values != values {
+                    continue;
+                }
+                let z = shared.trailing_zeros() as i8;
+                let seeing = if pivot_size == 3 { vec![pivot, first, second] } else { vec![first, second] };
+                let eliminations = seen_by_all(&candidates, &seeing, z);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells_involved: vec![pivot, first, second],
+                        fish: None,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+// Every cell that can still take the digit and sees all of the given cells
+fn seen_by_all(candidates: &[[u16; 9]; 9], cells: &[CellPosition], digit: i8) -> Vec<(CellPosition, i8)> {
+    positions()
+        .filter(|&cell| (mask(candidates, cell) >> digit) & 1u16 == 1)
+        .filter(|&cell| cells.iter().all(|&other| cell.sees(other)))
+        .map(|cell| (cell, digit))
+        .collect()
+}
+
+fn positions() -> impl Iterator<Item = CellPosition> {
+    (0..81).map(|index| CellPosition { row: index / 9, column: index % 9 })
+}
+
+fn mask(candidates: &[[u16; 9]; 9], cell: CellPosition) -> u16 {
+    candidates[cell.row as usize][cell.column as usize]
+}
@@ -0,0 +1,382 @@
+use std::ops::Not;
+
+use crate::solvers::{options::AbortCheck, stats::SolveStats};
+
+// A variable or its negation, stored as variable * 2 + (1 if negated)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn new(var: usize, positive: bool) -> Self {
+        Lit((var as u32) << 1 | !positive as u32)
+    }
+
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SatResult {
+    Sat,
+    Unsat,
+    // One of the limits in the AbortCheck was hit
+    Aborted,
+}
+
+// Conflicts before the first restart, later restarts are this times the Luby sequence
+const RESTART_BASE: u64 = 64;
+const ACTIVITY_DECAY: f64 = 0.95;
+
+// i-th element (0 based) of the Luby sequence 1 1 2 1 1 2 4 1 1 2 1 1 2 4 8 ...
+fn luby(mut i: u64) -> u64 {
+    let mut size = 1;
+    let mut power = 0;
+    while size < i + 1 {
+        power += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != i {
+        size = (size - 1) >> 1;
+        power -= 1;
+        i %= size;
+    }
+    1 << power
+}
+
+// Conflict driven clause learning: unit propagation over two watched literals per clause,
+// first UIP conflict analysis, VSIDS branching with phase saving, and Luby restarts.
+// Clauses can be added between calls to solve, which is how further solutions are found.
+pub struct Cdcl {
+    clauses: Vec<Vec<Lit>>,
+    // Clauses watching each literal, they are visited when that literal becomes false.
+    // The two watched literals of a clause are always its first two.
+    watches: Vec<Vec<usize>>,
+    assigns: Vec<Option<bool>>,
+    level: Vec<usize>,
+    // Clause that implied each variable, None for decisions and top level facts.
+    // The implied literal is always the first one in its reason clause.
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    // Where each decision level starts on the trail
+    trail_lim: Vec<usize>,
+    // Trail entries that haven't been propagated yet start here
+    queue_head: usize,
+    activity: Vec<f64>,
+    activity_inc: f64,
+    // Last value each variable had, decisions reuse it
+    polarity: Vec<bool>,
+    seen: Vec<bool>,
+    restarts: u64,
+    // An empty clause was found, nothing can satisfy the formula any more
+    unsat: bool,
+}
+
+impl Cdcl {
+    pub fn new(var_count: usize) -> Self {
+        Cdcl {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); var_count * 2],
+            assigns: vec![None; var_count],
+            level: vec![0; var_count],
+            reason: vec![None; var_count],
+            trail: Vec::with_capacity(var_count),
+            trail_lim: Vec::new(),
+            queue_head: 0,
+            activity: vec![0.0; var_count],
+            activity_inc: 1.0,
+            polarity: vec![false; var_count],
+            seen: vec![false; var_count],
+            restarts: 0,
+            unsat: false,
+        }
+    }
+
+    pub fn value(&self, var: usize) -> Option<bool> {
+        self.assigns[var]
+    }
+
+    fn lit_value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit.var()].map(|value| value == lit.is_positive())
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    // Returns false if the formula can no longer be satisfied
+    pub fn add_clause(&mut self, lits: &[Lit]) -> bool {
+        self.backtrack(0);
+        if self.unsat {
+            return false;
+        }
+
+        let mut clause: Vec<Lit> = Vec::with_capacity(lits.len());
+        for &lit in lits {
+            match self.lit_value(lit) {
+                // Already satisfied at the top level
+                Some(true) => return true,
+                Some(false) => {}
+                None if clause.contains(&!lit) => return true,
+                None if !clause.contains(&lit) => clause.push(lit),
+                None => {}
+            }
+        }
+
+        match clause.len() {
+            0 => self.unsat = true,
+            1 => {
+                self.enqueue(clause[0], None);
+                if self.propagate().is_some() {
+                    self.unsat = true;
+                }
+            }
+            _ => {
+                self.attach(clause);
+            }
+        }
+        !self.unsat
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+        index
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.assigns[var] = Some(lit.is_positive());
+        self.level[var] = self.decision_level();
+        self.reason[var] = reason;
+        self.trail.push(lit);
+    }
+
+    // Returns the conflicting clause, if any
+    fn propagate(&mut self) -> Option<usize> {
+        while self.queue_head < self.trail.len() {
+            let false_lit = !self.trail[self.queue_head];
+            self.queue_head += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut kept = 0;
+            let mut conflict = None;
+            let mut i = 0;
+            while i < watchers.len() {
+                let index = watchers[i];
+                i += 1;
+                let clause = &mut self.clauses[index];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                let first_value = self.assigns[first.var()].map(|value| value == first.is_positive());
+                if first_value == Some(true) {
+                    watchers[kept] = index;
+                    kept += 1;
+                    continue;
+                }
+
+                // Move the watch to any literal that isn't false
+                let assigns = &self.assigns;
+                let replacement = (2..clause.len()).find(|&k| {
+                    let lit = clause[k];
+                    assigns[lit.var()].map(|value| value == lit.is_positive()) != Some(false)
+                });
+                if let Some(k) = replacement {
+                    clause.swap(1, k);
+                    let new_watch = clause[1];
+                    self.watches[new_watch.index()].push(index);
+                    continue;
+                }
+
+                watchers[kept] = index;
+                kept += 1;
+                if first_value == Some(false) {
+                    conflict = Some(index);
+                    // Keep the watchers that weren't visited
+                    while i < watchers.len() {
+                        watchers[kept] = watchers[i];
+                        kept += 1;
+                        i += 1;
+                    }
+                } else {
+                    self.enqueue(first, Some(index));
+                }
+            }
+            watchers.truncate(kept);
+            self.watches[false_lit.index()] = watchers;
+
+            if conflict.is_some() {
+                self.queue_head = self.trail.len();
+                return conflict;
+            }
+        }
+        None
+    }
+
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.activity_inc;
+        if self.activity[var] > 1e100 {
+            for activity in &mut self.activity {
+                *activity *= 1e-100;
+            }
+            self.activity_inc *= 1e-100;
+        }
+    }
+
+    // Walks back from the conflict until only one literal of the current level is left (the first UIP).
+    // Returns the learnt clause, with the UIP first and the literal from the next highest level second,
+    // and the level to go back to.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        // The first slot is filled in with the UIP at the end
+        let mut learnt = vec![Lit(0)];
+        let mut at_current_level = 0;
+        let mut clause = conflict;
+        let mut implied: Option<Lit> = None;
+        let mut index = self.trail.len();
+
+        loop {
+            for k in 0..self.clauses[clause].len() {
+                let lit = self.clauses[clause][k];
+                if Some(lit) == implied {
+                    continue;
+                }
+                let var = lit.var();
+                if self.seen[var] || self.level[var] == 0 {
+                    continue;
+                }
+                self.seen[var] = true;
+                self.bump(var);
+                if self.level[var] == self.decision_level() {
+                    at_current_level += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+
+            // The next literal on the trail that is part of the conflict
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            self.seen[lit.var()] = false;
+            at_current_level -= 1;
+            if at_current_level == 0 {
+                learnt[0] = !lit;
+                break;
+            }
+            implied = Some(lit);
+            clause = self.reason[lit.var()].expect("only decisions have no reason");
+        }
+
+        for lit in &learnt[1..] {
+            self.seen[lit.var()] = false;
+        }
+
+        let mut backtrack_level = 0;
+        if learnt.len() > 1 {
+            let (highest, _) = learnt
+                .iter()
+                .enumerate()
+                .skip(1)
+                .max_by_key(|(_, lit)| self.level[lit.var()])
+                .expect("learnt has more than one literal");
+            learnt.swap(1, highest);
+            backtrack_level = self.level[learnt[1].var()];
+        }
+        (learnt, backtrack_level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for lit in self.trail.drain(start..) {
+            self.assigns[lit.var()] = None;
+            self.reason[lit.var()] = None;
+            self.polarity[lit.var()] = lit.is_positive();
+        }
+        self.trail_lim.truncate(level);
+        self.queue_head = self.trail.len();
+    }
+
+    // The unassigned variable with the highest activity
+    fn pick_branch_var(&self) -> Option<usize> {
+        (0..self.assigns.len())
+            .filter(|&var| self.assigns[var].is_none())
+            .max_by(|&a, &b| self.activity[a].total_cmp(&self.activity[b]))
+    }
+
+    // Searches for an assignment satisfying every clause. On Sat the assignment can be read with value(),
+    // until the next call to add_clause or solve.
+    // Decisions are counted as guesses and conflicts as backtracks.
+    pub fn solve(&mut self, abort_check: &mut AbortCheck, stats: &mut SolveStats) -> SatResult {
+        if self.unsat {
+            return SatResult::Unsat;
+        }
+        self.backtrack(0);
+        let mut conflicts_until_restart = RESTART_BASE * luby(self.restarts);
+
+        loop {
+            if abort_check.check(stats.guesses).is_some() {
+                return SatResult::Aborted;
+            }
+
+            let Some(conflict) = self.propagate() else {
+                let Some(var) = self.pick_branch_var() else {
+                    return SatResult::Sat;
+                };
+                stats.guesses += 1;
+                self.trail_lim.push(self.trail.len());
+                self.enqueue(Lit::new(var, self.polarity[var]), None);
+                continue;
+            };
+
+            stats.backtracks += 1;
+            if self.decision_level() == 0 {
+                self.unsat = true;
+                return SatResult::Unsat;
+            }
+            let (learnt, level) = self.analyze(conflict);
+            self.backtrack(level);
+            let implied = learnt[0];
+            if learnt.len() == 1 {
+                self.enqueue(implied, None);
+            } else {
+                let index = self.attach(learnt);
+                self.enqueue(implied, Some(index));
+            }
+            self.activity_inc /= ACTIVITY_DECAY;
+
+            conflicts_until_restart -= 1;
+            if conflicts_until_restart == 0 {
+                self.restarts += 1;
+                conflicts_until_restart = RESTART_BASE * luby(self.restarts);
+                self.backtrack(0);
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        sat::cdcl::{Cdcl, Lit, SatResult},
+        stats::SolveStats,
+    },
+};
+
+pub mod cdcl;
+#[cfg(test)]
+mod tests;
+
+// Encodes the board as CNF and hands it to the CDCL engine in cdcl.rs.
+// Variant rules only need to add their own clauses to the engine, rather than new propagation code.
+pub struct SatSolver;
+
+impl Solver for SatSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(SatSearch::new(board, options)))
+    }
+}
+
+pub const VAR_COUNT: usize = 729;
+
+// "The cell at row, column holds digit", all 0 based
+pub fn cell_var(row: usize, column: usize, digit: usize) -> usize {
+    row * 81 + column * 9 + digit
+}
+
+// Exactly one of the literals is true
+fn add_exactly_one(engine: &mut Cdcl, lits: &[Lit]) -> bool {
+    let mut satisfiable = engine.add_clause(lits);
+    for (i, &a) in lits.iter().enumerate() {
+        for &b in &lits[i + 1..] {
+            satisfiable &= engine.add_clause(&[!a, !b]);
+        }
+    }
+    satisfiable
+}
+
+// The classic rules: each cell has one digit, and each row, column and square has each digit once.
+// Returns false if the givens already make that impossible.
+pub fn encode(engine: &mut Cdcl, board: &Board<SimpleCell>) -> bool {
+    let mut satisfiable = true;
+    for row in 0..9 {
+        for column in 0..9 {
+            let lits: Vec<Lit> = (0..9).map(|digit| Lit::new(cell_var(row, column, digit), true)).collect();
+            satisfiable &= add_exactly_one(engine, &lits);
+        }
+    }
+    for digit in 0..9 {
+        for house in 0..9 {
+            let in_row: Vec<Lit> = (0..9).map(|column| Lit::new(cell_var(house, column, digit), true)).collect();
+            let in_column: Vec<Lit> = (0..9).map(|row| Lit::new(cell_var(row, house, digit), true)).collect();
+            let in_square: Vec<Lit> = (0..9)
+                .map(|i| Lit::new(cell_var((house / 3) * 3 + i / 3, (house % 3) * 3 + i % 3, digit), true))
+                .collect();
+            for lits in [in_row, in_column, in_square] {
+                satisfiable &= add_exactly_one(engine, &lits);
+            }
+        }
+    }
+
+    for (row, cells) in board.0.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            if let CellValue::Filled(value) = cell.value() {
+                satisfiable &= engine.add_clause(&[Lit::new(cell_var(row, column, *value as usize - 1), true)]);
+            }
+        }
+    }
+    satisfiable
+}
+
+pub struct SatSearch {
+    engine: Cdcl,
+    board: Board<SimpleCell>,
+    // No further solutions, either the engine proved it or the last one couldn't be blocked
+    exhausted: bool,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl SatSearch {
+    pub fn new(board: &Board<SimpleCell>, options: &SolveOptions) -> Self {
+        let start = Instant::now();
+        let mut engine = Cdcl::new(VAR_COUNT);
+        let exhausted = !encode(&mut engine, board);
+        let stats = SolveStats {
+            preprocess_time: start.elapsed(),
+            ..SolveStats::default()
+        };
+
+        SatSearch {
+            engine,
+            board: board.clone(),
+            exhausted,
+            stats,
+            abort_check: AbortCheck::new(options),
+        }
+    }
+
+    fn model(&self) -> Board<SimpleCell> {
+        let mut solution = self.board.clone();
+        for row in 0..9 {
+            for column in 0..9 {
+                if let Some(digit) = (0..9).find(|&digit| self.engine.value(cell_var(row, column, digit)) == Some(true)) {
+                    let position = CellPosition {
+                        row: row as i8,
+                        column: column as i8,
+                    };
+                    solution.get_mut_cell(&position).value = CellValue::Filled(digit as i8 + 1);
+                }
+            }
+        }
+        solution
+    }
+}
+
+impl Iterator for SatSearch {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        let start = Instant::now();
+        let result = self.engine.solve(&mut self.abort_check, &mut self.stats);
+        let solution = if result == SatResult::Sat {
+            let solution = self.model();
+            // Rule this exact board out, so the next call finds a different one
+            let blocking: Vec<Lit> = (0..VAR_COUNT)
+                .filter(|&var| self.engine.value(var) == Some(true))
+                .map(|var| Lit::new(var, false))
+                .collect();
+            self.exhausted = !self.engine.add_clause(&blocking);
+            Some(solution)
+        } else {
+            self.exhausted = result == SatResult::Unsat;
+            None
+        };
+        self.stats.search_time += start.elapsed();
+        solution
+    }
+}
+
+impl SolutionSearch for SatSearch {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::solvers::{
+    options::{AbortCheck, SolveOptions},
+    sat::cdcl::{Cdcl, Lit, SatResult},
+    stats::SolveStats,
+};
+
+fn solve(engine: &mut Cdcl) -> SatResult {
+    engine.solve(&mut AbortCheck::new(&SolveOptions::default()), &mut SolveStats::default())
+}
+
+// Every pigeon in some hole, and no two pigeons in the same hole
+fn pigeonhole(pigeons: usize, holes: usize) -> Cdcl {
+    let var = |pigeon: usize, hole: usize| pigeon * holes + hole;
+    let mut engine = Cdcl::new(pigeons * holes);
+    for pigeon in 0..pigeons {
+        let lits: Vec<Lit> = (0..holes).map(|hole| Lit::new(var(pigeon, hole), true)).collect();
+        engine.add_clause(&lits);
+    }
+    for hole in 0..holes {
+        for a in 0..pigeons {
+            for b in a + 1..pigeons {
+                engine.add_clause(&[Lit::new(var(a, hole), false), Lit::new(var(b, hole), false)]);
+            }
+        }
+    }
+    engine
+}
+
+#[test]
+fn finds_a_satisfying_assignment() {
+    let mut engine = pigeonhole(5, 5);
+    assert_eq!(solve(&mut engine), SatResult::Sat);
+    for hole in 0..5 {
+        let filled = (0..5).filter(|pigeon| engine.value(pigeon * 5 + hole) == Some(true)).count();
+        assert_eq!(filled, 1);
+    }
+}
+
+#[test]
+fn proves_unsatisfiable_formulas() {
+    let mut engine = pigeonhole(6, 5);
+    assert_eq!(solve(&mut engine), SatResult::Unsat);
+    assert_eq!(solve(&mut engine), SatResult::Unsat);
+
+    let mut engine = Cdcl::new(1);
+    assert!(engine.add_clause(&[Lit::new(0, true)]));
+    assert!(!engine.add_clause(&[Lit::new(0, false)]));
+    assert_eq!(solve(&mut engine), SatResult::Unsat);
+}
+
+#[test]
+fn clauses_can_be_added_between_solves() {
+    // x0 or x1, then rule out each model in turn
+    let mut engine = Cdcl::new(2);
+    engine.add_clause(&[Lit::new(0, true), Lit::new(1, true)]);
+    let mut models = 0;
+    while solve(&mut engine) == SatResult::Sat {
+        models += 1;
+        let blocking: Vec<Lit> = (0..2)
+            .map(|var| Lit::new(var, engine.value(var) != Some(true)))
+            .collect();
+        engine.add_clause(&blocking);
+    }
+    assert_eq!(models, 3);
+}
@@ -0,0 +1,204 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    solvers::{
+        SolutionSearch,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        rng::Rng,
+        stats::SolveStats,
+        strategy::{SearchStrategy, ValueOrder, VariableOrder, order_values, pick_cell},
+    },
+};
+
+// The increment/backtrack loop shared by the backtracking solvers.
+// Non fixed cells are filled from the top left to the bottom right, one value at a time.
+// If at any point the board is invalid, the last non fixed cell is incremented again,
+// and once a cell runs out of values it is emptied and the search moves back to the one before it.
+// Any other SearchStrategy keeps the cells it filled on a stack instead, see find_next_solution_ordered.
+pub struct BacktrackingSearch<T>
+where
+    T: IsCell,
+{
+    board: Board<T>,
+    is_valid: bool,
+    // Set when the board is already known to have no solution, nothing is searched
+    exhausted: bool,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+    strategy: SearchStrategy,
+    frames: Vec<Frame>,
+    variable_rng: Rng,
+    value_rng: Rng,
+}
+
+// A cell filled in by the ordered search, with the values to try in it and how many have been tried
+struct Frame {
+    position: CellPosition,
+    values: Vec<i8>,
+    tried: usize,
+}
+
+impl<T> BacktrackingSearch<T>
+where
+    T: IsCell,
+{
+    // `stats` holds whatever the pre-processor already did to the board
+    pub fn new(board: Board<T>, stats: SolveStats, options: &SolveOptions) -> Self {
+        let seed = |order_seed: Option<u64>| Rng::new(order_seed.unwrap_or_default());
+        BacktrackingSearch {
+            board,
+            is_valid: true,
+            exhausted: false,
+            stats,
+            abort_check: AbortCheck::new(options),
+            strategy: options.strategy,
+            frames: Vec::with_capacity(81),
+            variable_rng: seed(match options.strategy.variables {
+                VariableOrder::Random { seed } => Some(seed),
+                _ => None,
+            }),
+            value_rng: seed(match options.strategy.values {
+                ValueOrder::Random { seed } => Some(seed),
+                _ => None,
+            }),
+        }
+    }
+
+    // A search that finds nothing, for boards the pre-processor proved have no solution
+    pub fn exhausted(board: Board<T>, stats: SolveStats, options: &SolveOptions) -> Self {
+        BacktrackingSearch {
+            exhausted: true,
+            ..BacktrackingSearch::new(board, stats, options)
+        }
+    }
+
+    // Runs until the next solution is found, None once every value has been tried
+    // or one of the limits in the SolveOptions is hit.
+    // Calling it again carries on from the returned solution, so every solution is visited once.
+    pub fn next_solution(&mut self) -> Option<&Board<T>> {
+        if self.exhausted {
+            return None;
+        }
+        let start = Instant::now();
+        let found = if self.strategy == SearchStrategy::default() {
+            self.find_next_solution()
+        } else {
+            self.find_next_solution_ordered()
+        };
+        self.stats.search_time += start.elapsed();
+        if found { Some(&self.board) } else { None }
+    }
+
+    fn find_next_solution(&mut self) -> bool {
+        let mut curr_cell_pos: CellPosition;
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+
+            // If the current board is valid (no duplicate values)
+            curr_cell_pos = if self.is_valid {
+                if let Some(cell) = self.board.get_first_non_fixed_zero() {
+                    cell.position()
+                } else if self.board.is_correct() {
+                    // Resume by moving the last cell on to its next value
+                    self.is_valid = false;
+                    return true;
+                } else if let Some(cell) = self.board.get_last_non_fixed_non_zero() {
+                    cell.position()
+                } else {
+                    return false;
+                }
+            } else if let Some(cell) = self.board.get_last_non_fixed_non_zero() {
+                cell.position()
+            } else {
+                return false;
+            };
+
+            let mut_cell = self.board.get_mut_cell(&curr_cell_pos);
+            let increment_result = mut_cell.increment();
+
+            if increment_result.needs_revalidation {
+                // A new value was put in the cell
+                self.stats.guesses += 1;
+                self.stats.validity_checks += 1;
+                self.is_valid = self.board.is_cell_valid(curr_cell_pos);
+            } else {
+                if !increment_result.is_board_valid {
+                    self.stats.backtracks += 1;
+                }
+                self.is_valid = increment_result.is_board_valid;
+            }
+        }
+    }
+
+    // Picks each cell with the strategy's variable order and only tries values no peer holds yet,
+    // in the strategy's value order. Every value placed keeps the board valid,
+    // so a full board is a solution, and a cell with nothing to try means going back a cell.
+    fn find_next_solution_ordered(&mut self) -> bool {
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+
+            if self.is_valid {
+                let Some(position) = pick_cell(&self.board, self.strategy.variables, &mut self.variable_rng) else {
+                    // Resume by moving the last cell on to its next value
+                    self.is_valid = false;
+                    return true;
+                };
+                let values = order_values(&self.board, position, self.strategy.values, &mut self.value_rng);
+                self.frames.push(Frame {
+                    position,
+                    values,
+                    tried: 0,
+                });
+            }
+
+            let Some(frame) = self.frames.last_mut() else {
+                return false;
+            };
+            let cell = self.board.get_mut_cell(&frame.position);
+            let Some(&value) = frame.values.get(frame.tried) else {
+                cell.set_value(CellValue::Empty);
+                self.frames.pop();
+                self.stats.backtracks += 1;
+                self.is_valid = false;
+                continue;
+            };
+            frame.tried += 1;
+            self.stats.guesses += 1;
+            cell.set_value(CellValue::Filled(value));
+            self.is_valid = true;
+        }
+    }
+}
+
+// Yields every solution in turn, each next() call resumes the search where the last one stopped
+impl<T> Iterator for BacktrackingSearch<T>
+where
+    T: IsCell,
+{
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_solution().map(|solution| solution.convert())
+    }
+}
+
+impl<T> SolutionSearch for BacktrackingSearch<T>
+where
+    T: IsCell + Send,
+{
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
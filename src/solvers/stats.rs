@@ -0,0 +1,35 @@
+use std::{fmt, time::Duration};
+
+// Counters collected while solving, to compare how much work each solver does
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SolveStats {
+    // Cells the pre-processor managed to fix before the search started
+    pub preprocessed_cells: u64,
+    // Pushes onto the pre-processor's check queue, after the initial fill
+    pub queue_pushes: u64,
+    // Values the search tried in a cell
+    pub guesses: u64,
+    // Cells the search ran out of values for, so it had to step back to an earlier cell
+    pub backtracks: u64,
+    // Calls to Board::is_cell_valid
+    pub validity_checks: u64,
+    pub preprocess_time: Duration,
+    pub search_time: Duration,
+    // Set by the portfolio solver to the solver that answered first, whose stats these are
+    pub winner: Option<&'static str>,
+}
+
+impl fmt::Display for SolveStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(winner) = self.winner {
+            writeln!(f, "Winner: {}", winner.rsplit("::").next().unwrap_or(winner))?;
+        }
+        writeln!(f, "Pre-processed cells: {}", self.preprocessed_cells)?;
+        writeln!(f, "Queue pushes: {}", self.queue_pushes)?;
+        writeln!(f, "Guesses: {}", self.guesses)?;
+        writeln!(f, "Backtracks: {}", self.backtracks)?;
+        writeln!(f, "Validity checks: {}", self.validity_checks)?;
+        writeln!(f, "Pre-process time: {:?}", self.preprocess_time)?;
+        write!(f, "Search time: {:?}", self.search_time)
+    }
+}
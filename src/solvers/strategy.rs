@@ -0,0 +1,109 @@
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell},
+    },
+    solvers::rng::Rng,
+};
+
+// Which cell the backtracking solvers fill in next, and in which order they try its values.
+// The default is the original top left to bottom right, 1 to 9 search.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStrategy {
+    pub variables: VariableOrder,
+    pub values: ValueOrder,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VariableOrder {
+    // The first empty cell, top left to bottom right
+    #[default]
+    RowMajor,
+    // Minimum remaining values: the empty cell with the fewest values left
+    Mrv,
+    // Mrv, with ties going to the cell with the most empty peers
+    MrvDegree,
+    Random { seed: u64 },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueOrder {
+    #[default]
+    Ascending,
+    // Values that rule out the fewest options in the cell's empty peers first
+    LeastConstraining,
+    Random { seed: u64 },
+}
+
+// Values the cell allows that none of its peers already hold
+pub(crate) fn candidates<T>(board: &Board<T>, position: CellPosition) -> u16
+where
+    T: IsCell,
+{
+    let cell = &board.0[position.row as usize][position.column as usize];
+    let mut candidates = (1..=9)
+        .filter(|&value| cell.is_allowed(value))
+        .fold(0u16, |candidates, value| candidates | 1 << value);
+    for peer in position.peers() {
+        if let CellValue::Filled(value) = board.0[peer.row as usize][peer.column as usize].value() {
+            candidates &= !(1 << value);
+        }
+    }
+    candidates
+}
+
+fn is_open<T>(board: &Board<T>, position: CellPosition) -> bool
+where
+    T: IsCell,
+{
+    let cell = &board.0[position.row as usize][position.column as usize];
+    *cell.value() == CellValue::Empty && !cell.is_fixed()
+}
+
+// The next cell to fill in, None once every non fixed cell has a value
+pub(crate) fn pick_cell<T>(board: &Board<T>, order: VariableOrder, rng: &mut Rng) -> Option<CellPosition>
+where
+    T: IsCell,
+{
+    let mut open = (0..81)
+        .map(|index| CellPosition {
+            row: index / 9,
+            column: index % 9,
+        })
+        .filter(|&position| is_open(board, position));
+
+    match order {
+        VariableOrder::RowMajor => open.next(),
+        VariableOrder::Mrv => open.min_by_key(|&position| candidates(board, position).count_ones()),
+        VariableOrder::MrvDegree => open.min_by_key(|&position| {
+            let degree = position.peers().filter(|&peer| is_open(board, peer)).count();
+            (candidates(board, position).count_ones(), usize::MAX - degree)
+        }),
+        VariableOrder::Random { .. } => {
+            let open: Vec<CellPosition> = open.collect();
+            (!open.is_empty()).then(|| open[rng.below(open.len())])
+        }
+    }
+}
+
+// The cell's candidates, in the order they should be tried
+pub(crate) fn order_values<T>(board: &Board<T>, position: CellPosition, order: ValueOrder, rng: &mut Rng) -> Vec<i8>
+where
+    T: IsCell,
+{
+    let allowed = candidates(board, position);
+    let mut values: Vec<i8> = (1..=9).filter(|value| (allowed >> value) & 1 == 1).collect();
+    match order {
+        ValueOrder::Ascending => {}
+        ValueOrder::LeastConstraining => {
+            // How many empty peers would lose the value, stable so ties stay ascending
+            values.sort_by_cached_key(|&value| {
+                position.peers()
+                    .filter(|&peer| is_open(board, peer) && (candidates(board, peer) >> value) & 1 == 1)
+                    .count()
+            });
+        }
+        ValueOrder::Random { .. } => rng.shuffle(&mut values),
+    }
+    values
+}
@@ -1,24 +1,38 @@
-use crate::solvers::{cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver, BacktrackingSolver, Solver};
+use std::{
+    sync::{Arc, atomic::AtomicBool},
+    time::Duration,
+};
 
+use crate::board::{Board, cell::{CellPosition, CellValue, simple_cell::SimpleCell}};
+use crate::error::{Contradiction, SudokuError};
+use crate::solvers::{propagation::RULE_NAMES, backtracking_with_propagation::BacktrackingWithPropagationSolver, bitboard::BitboardSolver, logical::LogicalSolver, local_search::{annealing::AnnealingSolver, tabu::TabuSolver}, portfolio::PortfolioSolver, strategy::{SearchStrategy, ValueOrder, VariableOrder}, options::{AbortReason, SolveOptions}, cell_eliminated_backtracking::CellEliminatedBacktrackingSolver, dancing_links::DancingLinksSolver, forward_checking::ForwardCheckingSolver, group_eliminated_backtracking::GroupEliminatedBacktrackingSolver, sat::SatSolver, trail::TrailSolver, BacktrackingSolver, SolveOutcome, Solver};
 
-fn check_all_solvers(puzzle: &'static str, solution: Option<&'static str>) {
+
+fn check_all_solvers(puzzle: &str, solution: Option<&str>) {
     check_solver(BacktrackingSolver,puzzle, solution);
     check_solver(CellEliminatedBacktrackingSolver,puzzle, solution);
     check_solver(GroupEliminatedBacktrackingSolver,puzzle, solution);
+    check_solver(BacktrackingWithPropagationSolver::default(),puzzle, solution);
+    check_solver(TrailSolver,puzzle, solution);
+    check_solver(ForwardCheckingSolver,puzzle, solution);
+    check_solver(DancingLinksSolver,puzzle, solution);
+    check_solver(BitboardSolver,puzzle, solution);
+    check_solver(SatSolver,puzzle, solution);
 }
 
-fn check_solver<S: Solver>(solver: S, board: &'static str, expect: Option<&'static str>) {
+fn check_solver<S: Solver>(solver: S, board: &str, expect: Option<&str>) {
     let res = solver.solve(board);
     match expect {
         None => {
-            if res.is_ok_and(|ret| ret.0 == true) {
+            if res.as_ref().is_ok_and(|outcome| outcome.is_solved()) {
                 assert!(false, "{} solved impossible puzzle: {board}", solver.name())
             }
         },
         Some(solution) => {
             match res {
                 Err(error) => assert!(false, "{} failed to parse puzzle {board} with error: {error}", solver.name()),
-                Ok((_, actual)) => assert_eq!(actual, solution, "{} failed to solve puzzle {}", solver.name(), board)
+                Ok(SolveOutcome::Solved(actual)) => assert_eq!(actual.to_string(), solution, "{} failed to solve puzzle {}", solver.name(), board),
+                Ok(outcome) => panic!("{} failed to solve puzzle {board}: {outcome:?}", solver.name()),
             }
         }
     }
@@ -33,6 +47,448 @@ Some("89145763223468159767539284118397526476214895345926371894781632531852947652
 
 #[test]
 fn doesnt_solve_impossible_puzzle() {
-    check_all_solvers("4..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9", None);
+    // The two 4s in the first row clash, so the puzzle is rejected before any solver sees it
+    let res = BacktrackingSolver.solve("4..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9");
+    assert!(matches!(res, Err(SudokuError::ConflictingGivens { digit: 4, .. })), "{res:?}");
+}
+
+#[test]
+fn reports_unsolvable_puzzle() {
+    // r1c9 can't take any digit, but none of the givens clash
+    let puzzle = "12345678.........9...............................................................";
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver, &BacktrackingWithPropagationSolver::default(), &TrailSolver, &ForwardCheckingSolver, &DancingLinksSolver, &BitboardSolver, &SatSolver] {
+        assert_eq!(solver.solve(puzzle), Ok(SolveOutcome::Unsolvable), "{}", solver.name());
+    }
+}
+
+#[test]
+fn unsolvable_puzzle_is_not_searched_after_propagation() {
+    // The pre-processor already finds r1c9 empty, so no guesses are made
+    let puzzle = "12345678.........9...............................................................";
+    for solver in [&CellEliminatedBacktrackingSolver as &dyn Solver, &GroupEliminatedBacktrackingSolver, &BacktrackingWithPropagationSolver::default()] {
+        let (outcome, stats) = solver.solve_with_stats(puzzle).expect("valid puzzle");
+        assert_eq!((outcome, stats.guesses), (SolveOutcome::Unsolvable, 0), "{}", solver.name());
+    }
+}
+
+// The puzzle's givens, with the digits in `entries` filled in as a player's non fixed entries
+fn with_entries(puzzle: &str, entries: &[(i8, i8, i8)]) -> Board<SimpleCell> {
+    let mut board = Board::<SimpleCell>::parse_puzzle_string(puzzle).expect("valid puzzle");
+    for &(row, column, digit) in entries {
+        board.get_mut_cell(&CellPosition { row, column }).value = CellValue::Filled(digit);
+    }
+    board
+}
+
+#[test]
+fn keeps_player_entries() {
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let solution = "891457632234681597675392841183975264762148953459263718947816325318529476526734189";
+    // Right for this puzzle, r1c2 and r1c3 are 9 and 1
+    let board = with_entries(puzzle, &[(0, 1, 9), (0, 2, 1)]);
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver] {
+        match solver.solve_board(&board) {
+            Ok(SolveOutcome::Solved(actual)) => assert_eq!(actual.to_string(), solution, "{}", solver.name()),
+            outcome => panic!("{} failed with entries: {outcome:?}", solver.name()),
+        }
+    }
+
+    // Swapped, they don't clash with anything, but leave the search nothing to find
+    let board = with_entries(puzzle, &[(0, 1, 1), (0, 2, 9)]);
+    assert_eq!(BacktrackingSolver.solve_board(&board), Ok(SolveOutcome::Unsolvable));
+}
+
+#[test]
+fn reports_contradicting_entries() {
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    // r1c2 already has an 8 in its row
+    let board = with_entries(puzzle, &[(0, 1, 8)]);
+    let clash = Contradiction::Clash {
+        a: CellPosition { row: 0, column: 0 },
+        b: CellPosition { row: 0, column: 1 },
+        digit: 8,
+    };
+    for solver in [&CellEliminatedBacktrackingSolver as &dyn Solver, &GroupEliminatedBacktrackingSolver] {
+        assert_eq!(solver.solve_board(&board).err(), Some(SudokuError::Contradiction(clash.clone())), "{}", solver.name());
+    }
+
+    // With a 9 entered in r2c9, r1c9 has nothing left and 9 has nowhere to go in row 1
+    let board = with_entries("12345678.........................................................................", &[(1, 8, 9)]);
+    assert_eq!(
+        CellEliminatedBacktrackingSolver.solve_board(&board).err(),
+        Some(SudokuError::Contradiction(Contradiction::NoCandidates(CellPosition { row: 0, column: 8 })))
+    );
+    assert!(matches!(
+        GroupEliminatedBacktrackingSolver.solve_board(&board),
+        Err(SudokuError::Contradiction(Contradiction::NoCandidates(_) | Contradiction::NoPlaceFor { .. }))
+    ));
+
+    // The same board as givens alone is just unsolvable
+    let puzzle = "12345678.........9...............................................................";
+    assert_eq!(GroupEliminatedBacktrackingSolver.solve(puzzle), Ok(SolveOutcome::Unsolvable));
+}
+
+#[test]
+fn portfolio_reports_contradicting_entries() {
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let board = with_entries(puzzle, &[(0, 1, 8)]);
+    let clash = Contradiction::Clash {
+        a: CellPosition { row: 0, column: 0 },
+        b: CellPosition { row: 0, column: 1 },
+        digit: 8,
+    };
+    // Every member is started before the race, so the error isn't lost on a worker thread
+    assert_eq!(PortfolioSolver::default().solve_board(&board).err(), Some(SudokuError::Contradiction(clash)));
+
+    let portfolio = PortfolioSolver::from_names(&["dlx", "groupeliminated"]).expect("known solvers");
+    assert!(matches!(portfolio.solve_board(&board), Err(SudokuError::Contradiction(_))));
+}
+
+#[test]
+fn solves_borrowed_puzzle() {
+    // Built at runtime, the way puzzles read from a file are
+    let puzzle: String = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9"
+        .chars()
+        .collect();
+    check_all_solvers(
+        &puzzle,
+        Some("891457632234681597675392841183975264762148953459263718947816325318529476526734189"),
+    );
+}
+
+// Swapping the 9s and 3s in r1c2, r1c8, r2c2 and r2c8 gives a second solution
+const TWO_SOLUTIONS: &str = "8.14576.22.46815.7675392841183975264762148953459263718947816325318529476526734189";
+
+#[test]
+fn counts_solutions() {
+    let empty = ".".repeat(81);
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver, &BacktrackingWithPropagationSolver::default(), &TrailSolver, &ForwardCheckingSolver, &DancingLinksSolver, &BitboardSolver, &SatSolver] {
+        assert_eq!(solver.count_solutions(TWO_SOLUTIONS, 10), Ok(2), "{}", solver.name());
+        assert_eq!(solver.count_solutions(TWO_SOLUTIONS, 1), Ok(1), "{}", solver.name());
+        assert_eq!(solver.count_solutions(&empty, 25), Ok(25), "{}", solver.name());
+        assert_eq!(solver.has_unique_solution(TWO_SOLUTIONS), Ok(false), "{}", solver.name());
+        assert_eq!(
+            solver.has_unique_solution("8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9"),
+            Ok(true),
+            "{}",
+            solver.name()
+        );
+    }
+}
+
+#[test]
+fn solve_unique_reports_both_solutions() {
+    let outcome = GroupEliminatedBacktrackingSolver.solve_unique(TWO_SOLUTIONS);
+    let Ok(SolveOutcome::MultipleSolutions { first, second }) = outcome else {
+        panic!("expected two solutions, got {outcome:?}");
+    };
+    assert_ne!(first, second);
+    assert!(first.is_correct() && second.is_correct());
+}
+
+#[test]
+fn iterates_distinct_solutions_lazily() {
+    let puzzle = "123456789".to_string() + &".".repeat(72);
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver, &BacktrackingWithPropagationSolver::default(), &TrailSolver, &ForwardCheckingSolver, &DancingLinksSolver, &BitboardSolver, &SatSolver] {
+        let solutions: Vec<_> = solver.solutions(&puzzle).expect("valid puzzle").take(20).collect();
+        assert_eq!(solutions.len(), 20, "{}", solver.name());
+        for (index, solution) in solutions.iter().enumerate() {
+            assert!(solution.is_correct(), "{}", solver.name());
+            assert!(solution.to_string().starts_with("123456789"), "{}", solver.name());
+            assert!(!solution.to_string().contains('.'), "{}", solver.name());
+            assert!(!solutions[..index].contains(solution), "{} repeated a solution", solver.name());
+        }
+    }
+    let mut solutions = BacktrackingSolver.solutions(TWO_SOLUTIONS).expect("valid puzzle");
+    assert!(solutions.next().is_some());
+    assert!(solutions.next().is_some());
+    assert!(solutions.next().is_none());
+    assert!(solutions.next().is_none());
+}
+
+#[test]
+fn reports_search_stats() {
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+
+    let (outcome, stats) = BacktrackingSolver.solve_with_stats(puzzle).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    assert_eq!(stats.preprocessed_cells, 0);
+    assert_eq!(stats.queue_pushes, 0);
+    assert!(stats.guesses > 0 && stats.backtracks > 0);
+    assert_eq!(stats.validity_checks, stats.guesses);
+
+    for solver in [&CellEliminatedBacktrackingSolver as &dyn Solver, &GroupEliminatedBacktrackingSolver] {
+        let (outcome, eliminated_stats) = solver.solve_with_stats(puzzle).expect("valid puzzle");
+        assert!(outcome.is_solved(), "{}", solver.name());
+        assert!(eliminated_stats.preprocessed_cells > 0, "{}", solver.name());
+        assert!(eliminated_stats.queue_pushes > 0, "{}", solver.name());
+        assert!(eliminated_stats.guesses < stats.guesses, "{}", solver.name());
+    }
+}
+
+const HARD: &str = ".....9.......4..5.68.........4....7....62........8.......9..8.6........3..3..52..";
+
+#[test]
+fn stops_at_the_limits_in_solve_options() {
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver] {
+        let options = SolveOptions { node_budget: Some(100), ..SolveOptions::default() };
+        let (outcome, stats) = solver.solve_with(HARD, &options).expect("valid puzzle");
+        assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget), "{}", solver.name());
+        assert_eq!(stats.guesses, 101, "{}", solver.name());
+
+        let options = SolveOptions { timeout: Some(Duration::from_millis(20)), ..SolveOptions::default() };
+        let (outcome, stats) = solver.solve_with(HARD, &options).expect("valid puzzle");
+        assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::Timeout), "{}", solver.name());
+        assert!(stats.guesses > 0, "{}", solver.name());
+
+        let options = SolveOptions { cancel: Some(Arc::new(AtomicBool::new(true))), ..SolveOptions::default() };
+        let (outcome, _) = solver.solve_with(HARD, &options).expect("valid puzzle");
+        assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::Cancelled), "{}", solver.name());
+    }
+}
+
+#[test]
+fn dlx_counts_every_cover() {
+    assert_eq!(DancingLinksSolver.count_solutions(TWO_SOLUTIONS, usize::MAX), Ok(2));
+    assert_eq!(DancingLinksSolver.count_solutions(HARD, usize::MAX), Ok(1));
+
+    let (outcome, stats) = DancingLinksSolver.solve_with_stats(HARD).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    assert!(stats.guesses > 0);
+    assert_eq!(stats.validity_checks, 0);
+
+    let options = SolveOptions { node_budget: Some(10), ..SolveOptions::default() };
+    let (outcome, stats) = DancingLinksSolver.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget));
+    assert_eq!(stats.guesses, 11);
+
+    let options = SolveOptions { cancel: Some(Arc::new(AtomicBool::new(true))), ..SolveOptions::default() };
+    let (outcome, _) = DancingLinksSolver.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::Cancelled));
+}
+
+#[test]
+fn bitboard_branches_on_the_most_constrained_cell() {
+    let (outcome, stats) = BitboardSolver.solve_with_stats(HARD).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    // The row major solvers need hundreds of thousands of guesses here
+    assert!(stats.guesses < 100, "{}", stats.guesses);
+    assert_eq!(BitboardSolver.count_solutions(TWO_SOLUTIONS, usize::MAX), Ok(2));
+
+    let options = SolveOptions { node_budget: Some(3), ..SolveOptions::default() };
+    let (outcome, stats) = BitboardSolver.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget));
+    assert_eq!(stats.guesses, 4);
+}
+
+#[test]
+fn trail_follows_the_backtracking_order() {
+    let (outcome, stats) = TrailSolver.solve_with_stats(HARD).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    // Filling in the peers left with one value cuts the guesses groupeliminated needs by orders of magnitude
+    assert!(stats.guesses < 100_000, "{}", stats.guesses);
+
+    // Going back through the trail has to free every value it filled in
+    let puzzle = "123456789".to_string() + &".".repeat(72);
+    let from_trail: Vec<_> = TrailSolver.solutions(&puzzle).expect("valid puzzle").take(50).collect();
+    let from_backtracking: Vec<_> = GroupEliminatedBacktrackingSolver.solutions(&puzzle).expect("valid puzzle").take(50).collect();
+    assert_eq!(from_trail, from_backtracking);
+
+    let options = SolveOptions { node_budget: Some(10), ..SolveOptions::default() };
+    let (outcome, stats) = TrailSolver.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget));
+    assert_eq!(stats.guesses, 11);
+}
+
+#[test]
+fn forward_checking_prunes_during_the_search() {
+    let (outcome, stats) = ForwardCheckingSolver.solve_with_stats(HARD).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    // groupeliminated needs around 2 million guesses here
+    assert!(stats.guesses < 1000, "{}", stats.guesses);
+
+    // Backtracking has to put every elimination back, or later solutions would be missed
+    assert_eq!(ForwardCheckingSolver.count_solutions(TWO_SOLUTIONS, usize::MAX), Ok(2));
+    let puzzle = "123456789".to_string() + &".".repeat(72);
+    let from_forward_checking: Vec<_> = ForwardCheckingSolver.solutions(&puzzle).expect("valid puzzle").take(50).collect();
+    let from_backtracking: Vec<_> = GroupEliminatedBacktrackingSolver.solutions(&puzzle).expect("valid puzzle").take(50).collect();
+    assert_eq!(from_forward_checking, from_backtracking);
+}
+
+#[test]
+fn sat_solves_and_enumerates() {
+    let (outcome, stats) = SatSolver.solve_with_stats(HARD).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    assert!(stats.guesses > 0);
+    assert_eq!(SatSolver.count_solutions(TWO_SOLUTIONS, usize::MAX), Ok(2));
+
+    // Decisions count as guesses, and an empty board needs plenty of them
+    let options = SolveOptions { node_budget: Some(3), ..SolveOptions::default() };
+    let (outcome, stats) = SatSolver.solve_with(&".".repeat(81), &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget));
+    assert_eq!(stats.guesses, 4);
+}
+
+#[test]
+fn local_search_finds_a_solution_within_its_budget() {
+    let puzzle = ".83.2..9....8..1...293....8....987...7.....6...674....3....698...2..5....1..3.54.";
+    let solution = "183524697547869123629317458235698714471253869896741235354176982962485371718932546";
+    for solver in [&AnnealingSolver::default() as &dyn Solver, &TabuSolver::default()] {
+        let (outcome, stats) = solver.solve_with_stats(puzzle).expect("valid puzzle");
+        assert_eq!(outcome.solution().map(|board| board.to_string()).as_deref(), Some(solution), "{}", solver.name());
+        // Same seed, same run
+        assert_eq!(solver.solve_with_stats(puzzle).expect("valid puzzle").1.guesses, stats.guesses, "{}", solver.name());
+
+        assert_eq!(solver.count_solutions(puzzle, 2), Err(SudokuError::NotExhaustive(solver.name())));
+        assert_eq!(solver.solve_unique(puzzle), Err(SudokuError::NotExhaustive(solver.name())));
+    }
+
+    // It can't prove the puzzle has no solution, so it just runs out of steps
+    let unsolvable = "12345678.........9...............................................................";
+    let annealing = AnnealingSolver { max_steps: 1000, ..AnnealingSolver::default() };
+    let tabu = TabuSolver { max_steps: 100, ..TabuSolver::default() };
+    for (solver, steps) in [(&annealing as &dyn Solver, 1000), (&tabu, 100)] {
+        let (outcome, stats) = solver.solve_with_stats(unsolvable).expect("valid puzzle");
+        assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::StepBudget), "{}", solver.name());
+        assert_eq!(stats.guesses, steps, "{}", solver.name());
+    }
+}
+
+#[test]
+fn portfolio_takes_the_first_answer() {
+    let portfolio = PortfolioSolver::from_names(&["backtracking", "dlx"]).expect("known solvers");
+    let (outcome, stats) = portfolio.solve_with_stats(HARD).expect("valid puzzle");
+    assert_eq!(outcome, DancingLinksSolver.solve(HARD).expect("valid puzzle"));
+    assert_eq!(stats.winner, Some(DancingLinksSolver.name()));
+
+    let unsolvable = "12345678.........9...............................................................";
+    let (outcome, stats) = PortfolioSolver::default().solve_with_stats(unsolvable).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Unsolvable);
+    assert!(stats.winner.is_some());
+
+    // Every solver gives up, so the portfolio does too
+    let options = SolveOptions { node_budget: Some(1), ..SolveOptions::default() };
+    let (outcome, stats) = portfolio.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::NodeBudget));
+    assert_eq!(stats.winner, None);
+
+    let options = SolveOptions { cancel: Some(Arc::new(AtomicBool::new(true))), ..SolveOptions::default() };
+    let slow = PortfolioSolver::new(vec![Box::new(BacktrackingSolver), Box::new(GroupEliminatedBacktrackingSolver)]);
+    let (outcome, _) = slow.solve_with(HARD, &options).expect("valid puzzle");
+    assert_eq!(outcome, SolveOutcome::Aborted(AbortReason::Cancelled));
+
+    assert!(PortfolioSolver::from_names(&["dlx", "nope"]).is_err());
+}
+
+#[test]
+fn search_strategies_change_the_order_not_the_answer() {
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let solution = "891457632234681597675392841183975264762148953459263718947816325318529476526734189";
+    let board = Board::parse_puzzle_string(TWO_SOLUTIONS).expect("valid puzzle");
+    let variable_orders = [VariableOrder::RowMajor, VariableOrder::Mrv, VariableOrder::MrvDegree, VariableOrder::Random { seed: 7 }];
+    let value_orders = [ValueOrder::Ascending, ValueOrder::LeastConstraining, ValueOrder::Random { seed: 7 }];
+    for solver in [&BacktrackingSolver as &dyn Solver, &CellEliminatedBacktrackingSolver, &GroupEliminatedBacktrackingSolver] {
+        for variables in variable_orders {
+            for values in value_orders {
+                let options = SolveOptions { strategy: SearchStrategy { variables, values }, ..SolveOptions::default() };
+                let solutions: Vec<_> = solver.search(&board, &options).expect("valid puzzle").collect();
+                assert_eq!(solutions.len(), 2, "{} with {variables:?} and {values:?}", solver.name());
+                assert_ne!(solutions[0], solutions[1]);
+                assert!(solutions.iter().all(|solution| solution.is_correct()));
+
+                // A random cell order has nothing guiding it, so it is only fit for nearly solved boards
+                if variables == (VariableOrder::Random { seed: 7 }) {
+                    continue;
+                }
+                let (outcome, _) = solver.solve_with(puzzle, &options).expect("valid puzzle");
+                assert_eq!(
+                    outcome.solution().map(|board| board.to_string()).as_deref(),
+                    Some(solution),
+                    "{} with {variables:?} and {values:?}",
+                    solver.name()
+                );
+            }
+        }
+    }
+
+    // groupeliminated needs around 2 million guesses in row major order
+    let options = SolveOptions {
+        strategy: SearchStrategy { variables: VariableOrder::MrvDegree, values: ValueOrder::Ascending },
+        ..SolveOptions::default()
+    };
+    let (outcome, stats) = GroupEliminatedBacktrackingSolver.solve_with(HARD, &options).expect("valid puzzle");
+    assert!(outcome.is_solved());
+    assert!(stats.guesses < 10_000, "{}", stats.guesses);
+}
+
+#[test]
+fn logical_solver_explains_every_step() {
+    let solution = "891457632234681597675392841183975264762148953459263718947816325318529476526734189";
+    // The solution with a few cells taken out, each of them a single
+    let puzzle: String = solution
+        .chars()
+        .enumerate()
+        .map(|(index, digit)| if index % 7 == 0 { '.' } else { digit })
+        .collect();
+    let solved = LogicalSolver::default().solve(&puzzle).expect("valid puzzle");
+    assert!(solved.is_solved());
+    assert_eq!(solved.board.to_string(), solution);
+    assert_eq!(solved.steps.len(), puzzle.matches('.').count());
+    assert!(solved.steps.iter().all(|step| step.placements.len() == 1));
+
+    // Singles aren't enough here, so it stops instead of guessing
+    let puzzle = "8..45.6...3.68.......3.28.11.....2...6.....5...9.....89.78.6.......29.7...6.34..9";
+    let singles = LogicalSolver::from_names(&["naked-single", "hidden-single"]).expect("known rules");
+    let stuck = singles.solve(puzzle).expect("valid puzzle");
+    assert!(!stuck.is_solved());
+    assert!(!stuck.steps.is_empty());
+    for step in &stuck.steps {
+        for &(position, digit) in &step.placements {
+            let index = (position.row * 9 + position.column) as usize;
+            assert_eq!(solution.as_bytes()[index], b'0' + digit as u8, "{step}");
+        }
+    }
+    let naked_only = LogicalSolver::from_names(&["naked-single"]).expect("known rule").solve(puzzle).expect("valid puzzle");
+    assert!(naked_only.steps.len() < stuck.steps.len());
+    assert!(naked_only.steps.iter().all(|step| step.technique == "Naked single"));
+    // Locked candidates take it the rest of the way
+    assert!(LogicalSolver::default().solve(puzzle).expect("valid puzzle").is_solved());
+
+    // A wrong entry shows up as a contradiction, not as steps
+    let board = with_entries(puzzle, &[(0, 1, 8)]);
+    assert!(matches!(LogicalSolver::default().solve_board(&board), Err(SudokuError::Contradiction(_))));
+}
+
+#[test]
+fn group_eliminated_uses_subsets() {
+    // Needs a naked pair before any single shows up
+    let puzzle = "4.....938.32.941...953..24.37.6.9..4529..16736.47.3.9.957..83....39..4..24..3.7.9";
+    let (outcome, stats) = GroupEliminatedBacktrackingSolver.solve_with_stats(puzzle).expect("valid puzzle");
+    assert_eq!(
+        outcome.solution().map(ToString::to_string).as_deref(),
+        Some("461572938732894156895316247378629514529481673614753892957248361183967425246135789")
+    );
+    assert_eq!(stats.preprocessed_cells as usize, puzzle.matches('.').count());
+    assert_eq!(stats.backtracks, 0);
+
+    let (_, stats) = CellEliminatedBacktrackingSolver.solve_with_stats(puzzle).expect("valid puzzle");
+    assert_eq!(stats.preprocessed_cells, 0);
+}
+
+#[test]
+fn logical_solver_needs_a_fish_for_the_hard_puzzle() {
+    let solved = LogicalSolver::default().solve(HARD).expect("valid puzzle");
+    assert!(solved.is_solved());
+    assert_eq!(
+        solved.board.to_string(),
+        "345279681172846359689351724264593178918627435537184962751932846426718593893465217"
+    );
+    assert!(solved.steps.iter().any(|step| step.fish.is_some()));
+
+    let without_fish: Vec<_> = RULE_NAMES
+        .into_iter()
+        .filter(|name| !["x-wing", "swordfish", "jellyfish"].iter().any(|fish| name.ends_with(fish)))
+        .collect();
+    let stuck = LogicalSolver::from_names(&without_fish).expect("known rules").solve(HARD).expect("valid puzzle");
+    assert!(!stuck.is_solved());
 }
-    
\ No newline at end of file
@@ -0,0 +1,221 @@
+use std::time::Instant;
+
+use crate::{
+    board::{
+        Board,
+        cell::{CellPosition, CellValue, IsCell, simple_cell::SimpleCell},
+    },
+    error::SudokuError,
+    solvers::{
+        SolutionSearch, Solver,
+        options::{AbortCheck, AbortReason, SolveOptions},
+        stats::SolveStats,
+        units::BitGrid,
+    },
+};
+
+// Fills the empty cells in the same top left to bottom right order as the backtracking solver,
+// without re-scanning the board. The empty cells are listed once up front, the values used in each
+// row, column and square are kept as bit masks, and every change is pushed onto a trail,
+// so going back to a decision just pops the trail down to where it was.
+// Placing a value only looks at the cell's 20 peers: any of them left with a single value is filled in too,
+// and any left with none means the value was wrong.
+pub struct TrailSolver;
+
+impl Solver for TrailSolver {
+    fn search<'a>(
+        &'a self,
+        board: &Board<SimpleCell>,
+        options: &SolveOptions,
+    ) -> Result<Box<dyn SolutionSearch + 'a>, SudokuError> {
+        Ok(Box::new(TrailSearch::new(board, options)))
+    }
+}
+
+struct Decision {
+    // Position of the cell in TrailSearch::empty
+    cursor: usize,
+    // Values already tried in the cell
+    tried: u16,
+    trail_len: usize,
+}
+
+pub struct TrailSearch {
+    board: Board<SimpleCell>,
+    grid: BitGrid,
+    peers: [[u8; 20]; 81],
+    // The cells that were empty in the puzzle, in the order decisions are made
+    empty: Vec<usize>,
+    // Every entry before this in `empty` is filled
+    cursor: usize,
+    // Cells filled since the start, by decisions or by being the only value left
+    trail: Vec<usize>,
+    decisions: Vec<Decision>,
+    // Cells place still has to fill in, kept between calls so it is only allocated once
+    forced: Vec<(usize, u8)>,
+    // Set when the last board was a dead end or a solution, so the search has to move past it
+    backtracking: bool,
+    stats: SolveStats,
+    abort_check: AbortCheck,
+}
+
+impl TrailSearch {
+    pub fn new(board: &Board<SimpleCell>, options: &SolveOptions) -> Self {
+        let start = Instant::now();
+        let mut search = TrailSearch {
+            board: board.clone(),
+            grid: BitGrid::new(),
+            peers: [[0; 20]; 81],
+            empty: Vec::with_capacity(81),
+            cursor: 0,
+            trail: Vec::with_capacity(81),
+            decisions: Vec::with_capacity(81),
+            forced: Vec::with_capacity(81),
+            backtracking: false,
+            stats: SolveStats::default(),
+            abort_check: AbortCheck::new(options),
+        };
+
+        for (index, cell_peers) in search.peers.iter_mut().enumerate() {
+            let position = CellPosition {
+                row: (index / 9) as i8,
+                column: (index % 9) as i8,
+            };
+            for (slot, peer) in cell_peers.iter_mut().zip(position.peers()) {
+                *slot = (peer.row * 9 + peer.column) as u8;
+            }
+        }
+        for (index, cell) in board.0.iter().flatten().enumerate() {
+            match cell.value() {
+                CellValue::Filled(value) if (search.grid.candidates(index) >> value) & 1 == 1 => {
+                    search.grid.set(index, *value as u8);
+                }
+                // Two givens clash, so there is nothing to search
+                CellValue::Filled(_) => search.backtracking = true,
+                CellValue::Empty => search.empty.push(index),
+            }
+        }
+
+        search.stats.preprocess_time = start.elapsed();
+        search
+    }
+
+    // Places the value and then every peer it leaves with a single value, and so on.
+    // Returns false if some empty cell ends up with no values at all.
+    fn place(&mut self, index: usize, value: u8) -> bool {
+        self.forced.clear();
+        self.forced.push((index, value));
+        while let Some((index, value)) = self.forced.pop() {
+            // Filled in the meantime, or can't take the value any more
+            if self.grid.cells[index] != 0 {
+                continue;
+            }
+            if (self.grid.candidates(index) >> value) & 1 == 0 {
+                return false;
+            }
+            self.grid.set(index, value);
+            self.trail.push(index);
+
+            for &peer in &self.peers[index] {
+                let peer = peer as usize;
+                if self.grid.cells[peer] != 0 {
+                    continue;
+                }
+                let candidates = self.grid.candidates(peer);
+                match candidates.count_ones() {
+                    0 => return false,
+                    1 => self.forced.push((peer, candidates.trailing_zeros() as u8)),
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn undo_to(&mut self, trail_len: usize) {
+        while self.trail.len() > trail_len {
+            let Some(index) = self.trail.pop() else {
+                break;
+            };
+            self.grid.clear(index);
+        }
+    }
+
+    fn find_next_solution(&mut self) -> bool {
+        loop {
+            if self.abort_check.check(self.stats.guesses).is_some() {
+                return false;
+            }
+
+            if !self.backtracking {
+                while self.cursor < self.empty.len() && self.grid.cells[self.empty[self.cursor]] != 0 {
+                    self.cursor += 1;
+                }
+                if self.cursor == self.empty.len() {
+                    // Resume by moving the last decision on to its next value
+                    self.backtracking = true;
+                    return true;
+                }
+                self.decisions.push(Decision {
+                    cursor: self.cursor,
+                    tried: 0,
+                    trail_len: self.trail.len(),
+                });
+            }
+
+            let Some(&Decision { cursor, tried, trail_len }) = self.decisions.last() else {
+                return false;
+            };
+            self.undo_to(trail_len);
+            let index = self.empty[cursor];
+            let untried = self.grid.candidates(index) & !tried;
+            if untried == 0 {
+                self.decisions.pop();
+                self.stats.backtracks += 1;
+                self.backtracking = true;
+                continue;
+            }
+            let value = untried.trailing_zeros() as u8;
+            if let Some(decision) = self.decisions.last_mut() {
+                decision.tried |= 1 << value;
+            }
+
+            self.stats.guesses += 1;
+            self.cursor = cursor + 1;
+            self.backtracking = !self.place(index, value);
+        }
+    }
+}
+
+impl Iterator for TrailSearch {
+    type Item = Board<SimpleCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let found = self.find_next_solution();
+        self.stats.search_time += start.elapsed();
+        if !found {
+            return None;
+        }
+
+        let mut solution = self.board.clone();
+        for &index in &self.empty {
+            let position = CellPosition {
+                row: (index / 9) as i8,
+                column: (index % 9) as i8,
+            };
+            solution.get_mut_cell(&position).value = CellValue::Filled(self.grid.cells[index] as i8);
+        }
+        Some(solution)
+    }
+}
+
+impl SolutionSearch for TrailSearch {
+    fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    fn aborted(&self) -> Option<AbortReason> {
+        self.abort_check.aborted()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::board::{Group, cell::CellPosition};
+
+// Units 0-8 are the rows, 9-17 the columns and 18-26 the squares
+pub(crate) fn unit_cells(unit: i8) -> impl Iterator<Item = CellPosition> {
+    match unit {
+        0..9 => Group::Row(unit),
+        9..18 => Group::Column(unit - 9),
+        _ => Group::Square(unit - 18),
+    }
+    .cells()
+}
+
+// Bits 1 to 9, same layout as the forbidden masks in ConstrainedCell
+pub(crate) const ALL_VALUES: u16 = 0b11_1111_1110;
+
+// The board as 81 digits, with the values used in each row, column and square kept as bit masks
+// so the candidates of a cell are a couple of ORs away
+#[derive(Clone)]
+pub(crate) struct BitGrid {
+    // 0 for an empty cell, row major
+    pub(crate) cells: [u8; 81],
+    row_used: [u16; 9],
+    col_used: [u16; 9],
+    square_used: [u16; 9],
+}
+
+impl BitGrid {
+    pub(crate) fn new() -> Self {
+        BitGrid {
+            cells: [0; 81],
+            row_used: [0; 9],
+            col_used: [0; 9],
+            square_used: [0; 9],
+        }
+    }
+
+    pub(crate) fn candidates(&self, index: usize) -> u16 {
+        !(self.row_used[index / 9] | self.col_used[index % 9] | self.square_used[square_index(index)])
+            & ALL_VALUES
+    }
+
+    pub(crate) fn set(&mut self, index: usize, value: u8) {
+        self.cells[index] = value;
+        self.row_used[index / 9] |= 1 << value;
+        self.col_used[index % 9] |= 1 << value;
+        self.square_used[square_index(index)] |= 1 << value;
+    }
+
+    // Empties the cell, its value can go in its row, column and square again
+    pub(crate) fn clear(&mut self, index: usize) {
+        let value = self.cells[index];
+        self.cells[index] = 0;
+        self.row_used[index / 9] &= !(1 << value);
+        self.col_used[index % 9] &= !(1 << value);
+        self.square_used[square_index(index)] &= !(1 << value);
+    }
+}
+
+fn square_index(index: usize) -> usize {
+    (index / 27) * 3 + (index % 9) / 3
+}